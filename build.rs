@@ -0,0 +1,22 @@
+//! Detect `std` interfaces that are newer than our minimum supported Rust version.
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    // `std::io::Seek::seek_relative`, stable since Rust 1.80.
+    println!("cargo:rustc-check-cfg=cfg(not_io_seek_relative)");
+
+    if rustc_minor_version().map_or(false, |minor| minor >= 80) {
+        println!("cargo:rustc-cfg=not_io_seek_relative");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // The output looks like `rustc 1.80.0 (051478957 2024-07-21)`.
+    version.split('.').nth(1)?.parse().ok()
+}
@@ -0,0 +1,216 @@
+use core::mem::MaybeUninit;
+
+/// A borrowed byte buffer that tracks which of its bytes have been filled and initialized.
+///
+/// This replicates the unstable `core::io::BorrowedBuf`. The buffer is divided into three parts:
+/// a filled prefix of data that has been read, followed by bytes that are initialized but not yet
+/// filled, and finally the uninitialized remainder. Readers write into the unfilled part through a
+/// `BorrowedCursor`, which avoids zeroing memory that a reader fills anyways.
+pub struct BorrowedBuf<'data> {
+    buf: &'data mut [MaybeUninit<u8>],
+    filled: usize,
+    init: usize,
+}
+
+/// A writable view of the unfilled part of a `BorrowedBuf`.
+///
+/// Data can only be appended, the cursor never exposes or modifies the filled part of the buffer
+/// it was created from.
+pub struct BorrowedCursor<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: &'a mut usize,
+    init: &'a mut usize,
+    start: usize,
+}
+
+impl<'data> From<&'data mut [u8]> for BorrowedBuf<'data> {
+    fn from(buf: &'data mut [u8]) -> Self {
+        let init = buf.len();
+        // SAFETY: initialized memory is valid uninitialized memory, and we never de-initialize.
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        BorrowedBuf {
+            buf,
+            filled: 0,
+            init,
+        }
+    }
+}
+
+impl<'data> From<&'data mut [MaybeUninit<u8>]> for BorrowedBuf<'data> {
+    fn from(buf: &'data mut [MaybeUninit<u8>]) -> Self {
+        BorrowedBuf {
+            buf,
+            filled: 0,
+            init: 0,
+        }
+    }
+}
+
+impl<'data> BorrowedBuf<'data> {
+    /// The total length of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The length of the filled part.
+    pub fn len(&self) -> usize {
+        self.filled
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// The length of the initialized part, which includes the filled part.
+    pub fn init_len(&self) -> usize {
+        self.init
+    }
+
+    pub fn filled(&self) -> &[u8] {
+        // SAFETY: the filled part is always initialized.
+        unsafe { &*(&self.buf[..self.filled] as *const [MaybeUninit<u8>] as *const [u8]) }
+    }
+
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the filled part is always initialized.
+        unsafe { &mut *(&mut self.buf[..self.filled] as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Get a cursor for appending to the unfilled part of the buffer.
+    pub fn unfilled(&mut self) -> BorrowedCursor<'_> {
+        BorrowedCursor {
+            start: self.filled,
+            buf: self.buf,
+            filled: &mut self.filled,
+            init: &mut self.init,
+        }
+    }
+
+    /// Reset the filled part to be empty, keeping the bytes initialized.
+    pub fn clear(&mut self) -> &mut Self {
+        self.filled = 0;
+        self
+    }
+
+    /// Assert that the first `n` bytes of the buffer are initialized.
+    ///
+    /// This never shrinks the initialized part.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the first `n` bytes of the buffer have been initialized.
+    pub unsafe fn set_init(&mut self, n: usize) -> &mut Self {
+        self.init = self.init.max(n);
+        self
+    }
+}
+
+impl<'a> BorrowedCursor<'a> {
+    /// Reborrow the cursor, such that it can be passed by value while retaining this one.
+    pub fn reborrow(&mut self) -> BorrowedCursor<'_> {
+        BorrowedCursor {
+            buf: self.buf,
+            filled: self.filled,
+            init: self.init,
+            start: self.start,
+        }
+    }
+
+    /// The number of bytes that can still be appended.
+    pub fn capacity(&self) -> usize {
+        self.buf.len() - *self.filled
+    }
+
+    /// The number of bytes that have been appended through this cursor and its reborrows.
+    pub fn written(&self) -> usize {
+        *self.filled - self.start
+    }
+
+    /// The initialized part of the unfilled buffer.
+    pub fn init_mut(&mut self) -> &mut [u8] {
+        let unfilled = &mut self.buf[*self.filled..*self.init];
+        // SAFETY: all bytes up to `init` are initialized.
+        unsafe { &mut *(unfilled as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Zero all uninitialized bytes, such that `init_mut` covers the whole capacity.
+    pub fn ensure_init(&mut self) -> &mut Self {
+        for byte in &mut self.buf[*self.init..] {
+            *byte = MaybeUninit::new(0);
+        }
+        *self.init = self.buf.len();
+        self
+    }
+
+    /// The whole unfilled part of the buffer, possibly uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must not de-initialize any bytes that have been initialized before.
+    pub unsafe fn as_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        &mut self.buf[*self.filled..]
+    }
+
+    /// Mark `n` initialized bytes at the start of the unfilled part as filled.
+    ///
+    /// # Panics
+    ///
+    /// When `n` is larger than the length of `init_mut`.
+    pub fn advance(&mut self, n: usize) -> &mut Self {
+        assert!(
+            n <= *self.init - *self.filled,
+            "advancing beyond initialized bytes"
+        );
+        *self.filled += n;
+        self
+    }
+
+    /// Mark `n` bytes at the start of the unfilled part as filled and initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that these bytes have been initialized.
+    pub unsafe fn advance_unchecked(&mut self, n: usize) -> &mut Self {
+        *self.filled += n;
+        *self.init = (*self.init).max(*self.filled);
+        self
+    }
+
+    /// Assert that the first `n` bytes of the unfilled part are initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that these bytes have been initialized.
+    pub unsafe fn set_init(&mut self, n: usize) -> &mut Self {
+        *self.init = (*self.init).max(*self.filled + n);
+        self
+    }
+
+    /// Copy all of `data` to the unfilled part and mark it as filled.
+    ///
+    /// # Panics
+    ///
+    /// When `data` is longer than the remaining capacity.
+    pub fn append(&mut self, data: &[u8]) {
+        assert!(
+            self.capacity() >= data.len(),
+            "appending beyond the capacity"
+        );
+        let target = &mut self.buf[*self.filled..][..data.len()];
+        for (byte, &value) in target.iter_mut().zip(data) {
+            *byte = MaybeUninit::new(value);
+        }
+        // SAFETY: we have just initialized these bytes.
+        unsafe { self.advance_unchecked(data.len()) };
+    }
+}
+
+impl core::fmt::Debug for BorrowedBuf<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("BorrowedBuf")
+            .field("init", &self.init)
+            .field("filled", &self.filled)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
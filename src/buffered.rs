@@ -0,0 +1,285 @@
+use crate::{Error, ErrorKind, Result, Write};
+
+/// Adds buffering to a reader, with storage provided by the caller.
+///
+/// This mirrors `std::io::BufReader` but never allocates. The buffer `B` can be any byte storage
+/// such as `[u8; N]`, `&mut [u8]` or, with `alloc`, a `Vec<u8>`. Note that the capacity is the
+/// length of the storage as seen through `AsRef<[u8]>`, not the allocated capacity of a vector.
+pub struct BufReader<R, B> {
+    pub(crate) inner: R,
+    pub(crate) buf: B,
+    pub(crate) pos: usize,
+    pub(crate) filled: usize,
+}
+
+impl<R, B> BufReader<R, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Wrap a reader, using `buf` as storage for buffered data.
+    ///
+    /// The previous contents of the storage are ignored.
+    pub fn new(inner: R, buf: B) -> Self {
+        BufReader {
+            inner,
+            buf,
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader bypasses and may corrupt the buffered data.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the currently buffered data, without filling the buffer.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf.as_ref()[self.pos..self.filled]
+    }
+
+    /// The number of bytes the storage can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Unwrap the reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Unwrap both the reader and the storage, discarding any buffered data.
+    pub fn into_parts(self) -> (R, B) {
+        (self.inner, self.buf)
+    }
+
+    pub(crate) fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.filled = 0;
+    }
+}
+
+/// Adds buffering to a writer, with storage provided by the caller.
+///
+/// This mirrors `std::io::BufWriter` but never allocates. As with the `BufReader`, the capacity is
+/// the length of the storage `B`. Buffered data is flushed when the writer is dropped but any error
+/// is ignored at that point. Call `flush` or `into_inner` to observe errors instead.
+pub struct BufWriter<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> {
+    pub(crate) inner: W,
+    pub(crate) buf: B,
+    pub(crate) len: usize,
+    /// Set while calling into the inner writer, such that we do not retry in `drop` after it
+    /// panicked.
+    pub(crate) panicked: bool,
+}
+
+/// Wraps a writer and buffers its output, flushing whenever a newline is written.
+///
+/// This mirrors `std::io::LineWriter`, based on the `BufWriter` of this crate.
+pub struct LineWriter<W: Write, B: AsRef<[u8]> + AsMut<[u8]>> {
+    pub(crate) inner: BufWriter<W, B>,
+}
+
+/// The error returned from `into_inner` when the buffered data could not be written.
+///
+/// It returns the buffering writer itself, so that no buffered data is lost.
+#[derive(Debug)]
+pub struct IntoInnerError<W>(W, Error);
+
+impl<W: Write, B> BufWriter<W, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Wrap a writer, using `buf` as storage for buffered data.
+    ///
+    /// The previous contents of the storage are ignored.
+    pub fn new(inner: W, buf: B) -> Self {
+        BufWriter {
+            inner,
+            buf,
+            len: 0,
+            panicked: false,
+        }
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer bypasses any buffered data.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Return the data that is buffered but not yet written.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf.as_ref()[..self.len]
+    }
+
+    /// The number of bytes the storage can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Flush the buffer and unwrap the writer.
+    ///
+    /// If flushing fails, the error contains this writer with all data that was not yet written.
+    pub fn into_inner(mut self) -> core::result::Result<W, IntoInnerError<Self>> {
+        match self.flush_buf() {
+            Err(err) => Err(IntoInnerError(self, err)),
+            Ok(()) => Ok(self.into_parts().0),
+        }
+    }
+
+    /// Unwrap the writer and storage without flushing.
+    ///
+    /// Returns the writer, the storage and the number of unwritten bytes at the start of the
+    /// storage.
+    pub fn into_parts(self) -> (W, B, usize) {
+        let this = core::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped and both fields are read exactly once.
+        let (inner, buf) = unsafe { (core::ptr::read(&this.inner), core::ptr::read(&this.buf)) };
+        (inner, buf, this.len)
+    }
+
+    /// Write all buffered data to the inner writer.
+    ///
+    /// Data that was written is removed from the buffer even when an error occurs later on.
+    pub(crate) fn flush_buf(&mut self) -> Result<()> {
+        let mut written = 0;
+
+        let ret = loop {
+            if written >= self.len {
+                break Ok(());
+            }
+
+            self.panicked = true;
+            let r = self.inner.write(&self.buf.as_ref()[written..self.len]);
+            self.panicked = false;
+
+            match r {
+                Ok(0) => break Err(Error::from(ErrorKind::WriteZero)),
+                Ok(n) => written += n,
+                Err(ref e) if e.is_interrupted() => {}
+                Err(e) => break Err(e),
+            }
+        };
+
+        if written > 0 {
+            self.buf.as_mut().copy_within(written..self.len, 0);
+            self.len -= written;
+        }
+
+        ret
+    }
+
+    /// Write to the inner writer directly, bypassing the buffer.
+    pub(crate) fn write_unbuffered(&mut self, buf: &[u8]) -> Result<usize> {
+        self.panicked = true;
+        let r = self.inner.write(buf);
+        self.panicked = false;
+        r
+    }
+
+    /// Write all of `buf` to the inner writer directly, bypassing the buffer.
+    pub(crate) fn write_all_unbuffered(&mut self, buf: &[u8]) -> Result<()> {
+        self.panicked = true;
+        let r = self.inner.write_all(buf);
+        self.panicked = false;
+        r
+    }
+
+    /// Copy as much as fits into the spare storage, returning the amount.
+    pub(crate) fn write_to_buf(&mut self, buf: &[u8]) -> usize {
+        let spare = &mut self.buf.as_mut()[self.len..];
+        let n = spare.len().min(buf.len());
+        spare[..n].copy_from_slice(&buf[..n]);
+        self.len += n;
+        n
+    }
+}
+
+impl<W: Write, B> Drop for BufWriter<W, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn drop(&mut self) {
+        if !self.panicked {
+            // Dtors should not panic, so we ignore a failed flush.
+            let _r = self.flush_buf();
+        }
+    }
+}
+
+impl<W: Write, B> LineWriter<W, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Wrap a writer, using `buf` as storage for buffered data.
+    pub fn new(inner: W, buf: B) -> Self {
+        LineWriter {
+            inner: BufWriter::new(inner, buf),
+        }
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer bypasses any buffered data.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Flush the buffer and unwrap the writer.
+    ///
+    /// If flushing fails, the error contains this writer with all data that was not yet written.
+    pub fn into_inner(self) -> core::result::Result<W, IntoInnerError<Self>> {
+        self.inner
+            .into_inner()
+            .map_err(|err| err.new_wrapped(|inner| LineWriter { inner }))
+    }
+}
+
+impl<W> IntoInnerError<W> {
+    /// The error that occurred while flushing the buffer.
+    pub fn error(&self) -> &Error {
+        &self.1
+    }
+
+    pub fn into_error(self) -> Error {
+        self.1
+    }
+
+    /// The writer on which the flush was attempted, still holding the unwritten data.
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+
+    pub fn into_parts(self) -> (Error, W) {
+        (self.1, self.0)
+    }
+
+    pub(crate) fn new_wrapped<U>(self, f: impl FnOnce(W) -> U) -> IntoInnerError<U> {
+        IntoInnerError(f(self.0), self.1)
+    }
+}
+
+impl<W> core::fmt::Display for IntoInnerError<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.error(), f)
+    }
+}
@@ -29,3 +29,31 @@ impl<T> Cursor<T> {
         self.pos = pos;
     }
 }
+
+impl<T: AsRef<[u8]>> Cursor<T> {
+    /// The data after the current position, empty if the position is beyond the end.
+    pub fn remaining_slice(&self) -> &[u8] {
+        self.split_at_position().1
+    }
+
+    /// Check if there is no data left after the current position.
+    pub fn is_empty(&self) -> bool {
+        self.remaining_slice().is_empty()
+    }
+
+    /// Split the data into the part before and after the current position.
+    pub fn split_at_position(&self) -> (&[u8], &[u8]) {
+        let data = self.inner.as_ref();
+        let pos = self.pos.min(data.len() as u64) as usize;
+        data.split_at(pos)
+    }
+}
+
+impl<T: AsMut<[u8]>> Cursor<T> {
+    /// Split the data mutably into the part before and after the current position.
+    pub fn split_at_position_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        let data = self.inner.as_mut();
+        let pos = self.pos.min(data.len() as u64) as usize;
+        data.split_at_mut(pos)
+    }
+}
@@ -0,0 +1,78 @@
+//! Reading delimited data into caller provided buffers, without allocating.
+//!
+//! These are the counterparts of `BufRead::read_until` and `BufRead::read_line` for targets
+//! without `alloc`. Instead of growing a container, the data is copied into a slice and the filled
+//! prefix of that slice is returned.
+use super::{BufRead, Error, ErrorKind, Result};
+
+/// The error returned when the delimiter was not found within the buffer.
+const BUFFER_FULL: Error = Error::new_static(
+    ErrorKind::BufferFull,
+    "delimiter not found within the buffer",
+);
+
+/// Read all bytes up to and including the delimiter `byte` into `buf`.
+///
+/// Returns the filled prefix of `buf`. At the end of the stream the delimiter may be missing and
+/// the prefix is empty once no more data is available.
+///
+/// # Errors
+///
+/// When `buf` is filled completely before the delimiter was found, this fails with an error of
+/// kind `ErrorKind::BufferFull`. All of `buf` then contains consumed data, the remainder of the
+/// segment is left in the reader and can be discarded with `BufRead::skip_until`.
+pub fn read_until<'buf, R: BufRead + ?Sized>(
+    r: &mut R,
+    byte: u8,
+    buf: &'buf mut [u8],
+) -> Result<&'buf mut [u8]> {
+    let mut len = 0;
+
+    loop {
+        let available = match r.fill_buf() {
+            Ok(n) => n,
+            Err(ref e) if e.is_interrupted() => continue,
+            Err(e) => return Err(e),
+        };
+
+        if available.is_empty() {
+            return Ok(&mut buf[..len]);
+        }
+
+        let space = &mut buf[len..];
+        if space.is_empty() {
+            return Err(BUFFER_FULL);
+        }
+
+        let available = &available[..available.len().min(space.len())];
+        let (done, used) = match available.iter().position(|&b| b == byte) {
+            Some(n) => (true, n + 1),
+            None => (false, available.len()),
+        };
+
+        space[..used].copy_from_slice(&available[..used]);
+        r.consume(used);
+        len += used;
+
+        if done {
+            return Ok(&mut buf[..len]);
+        }
+    }
+}
+
+/// Read a line, including its terminating `\n`, into `buf`.
+///
+/// This behaves like `read_until` but additionally validates that the line is UTF-8.
+///
+/// # Errors
+///
+/// Fails with `ErrorKind::BufferFull` when the line does not fit into `buf`, and with
+/// `ErrorKind::InvalidData` if it is not valid UTF-8. The line has been consumed in the latter
+/// case.
+pub fn read_line<'buf, R: BufRead + ?Sized>(
+    r: &mut R,
+    buf: &'buf mut [u8],
+) -> Result<&'buf mut str> {
+    let line = read_until(r, b'\n', buf)?;
+    core::str::from_utf8_mut(line).map_err(|_| Error::from(ErrorKind::InvalidData))
+}
@@ -0,0 +1,69 @@
+/// An owned buffer of fixed capacity, the no-alloc counterpart of a `Vec<u8>`.
+///
+/// Writing appends to the data, until the capacity of `N` bytes is exhausted. Then writes fail
+/// with `ErrorKind::WriteZero`. Reading consumes the data from the front but leaves it in the
+/// buffer, the read position can be moved anywhere within the data by seeking.
+#[derive(Clone)]
+pub struct FixedBuf<const N: usize> {
+    pub(crate) buf: [u8; N],
+    pub(crate) len: usize,
+    pub(crate) pos: usize,
+}
+
+impl<const N: usize> FixedBuf<N> {
+    pub const fn new() -> Self {
+        FixedBuf {
+            buf: [0; N],
+            len: 0,
+            pos: 0,
+        }
+    }
+
+    /// All data that has been written.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len]
+    }
+
+    /// The number of bytes that have been written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The number of bytes that can still be written.
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    /// Remove all data and reset the read position.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.pos = 0;
+    }
+}
+
+impl<const N: usize> Default for FixedBuf<N> {
+    fn default() -> Self {
+        FixedBuf::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for FixedBuf<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("FixedBuf")
+            .field("data", &self.as_slice())
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::Error;
+
+/// Exposes a `Write` as a `core::fmt::Write`.
+///
+/// The formatting machinery can only report an opaque `core::fmt::Error`. The first error of the
+/// underlying writer is kept instead and can be retrieved with `take_error`.
+pub struct FmtWriter<W> {
+    pub(crate) inner: W,
+    pub(crate) error: Option<Error>,
+}
+
+/// Exposes a `core::fmt::Write`, such as a `Formatter`, as a `Write` of UTF-8 data.
+///
+/// Characters may be split across several calls to `write`, their leading bytes are then kept
+/// until the character is complete. Any other invalid UTF-8 is rejected with `InvalidData`.
+pub struct Utf8Writer<F> {
+    pub(crate) inner: F,
+    pub(crate) partial: [u8; 4],
+    pub(crate) partial_len: usize,
+}
+
+impl<W> FmtWriter<W> {
+    pub fn new(inner: W) -> Self {
+        FmtWriter { inner, error: None }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Retrieve the error that caused formatting to fail, if any.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<F> Utf8Writer<F> {
+    pub fn new(inner: F) -> Self {
+        Utf8Writer {
+            inner,
+            partial: [0; 4],
+            partial_len: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &F {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.inner
+    }
+
+    /// Unwrap the formatter, discarding the bytes of any incomplete character.
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
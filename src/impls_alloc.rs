@@ -1,6 +1,9 @@
 // FIXME: specialize impls? Many are copies from `impls_nostd_noalloc.rs`
-use super::{ErrorKind, Result};
-use crate::alloc::{string::String, vec::Vec};
+use super::{BorrowedBuf, BorrowedCursor, BufRead, Lines, Split};
+use super::{DuplexStream, PipeReader, PipeWriter, Read, Seek, SeekFrom, Write};
+use super::{ErrorKind, IoSlice, IoSliceMut, Result};
+use crate::alloc::{boxed::Box, collections::VecDeque, string::String, vec::Vec};
+use core::mem::MaybeUninit;
 
 impl super::Read for &'_ [u8] {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
@@ -9,6 +12,32 @@ impl super::Read for &'_ [u8] {
         *self = &self[len..];
         Ok(len)
     }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let len = self.len().min(cursor.capacity());
+        cursor.append(&self[..len]);
+        *self = &self[len..];
+        Ok(())
+    }
+
+    fn read_to_end_bounded(&mut self, buf: &mut Vec<u8>, max: usize) -> Result<usize> {
+        buf_read_to_end_bounded(self, buf, max)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let mut read = 0;
+        for buf in bufs {
+            read += self.read(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+        Ok(read)
+    }
 }
 
 impl super::BufRead for &'_ [u8] {
@@ -26,10 +55,21 @@ impl super::Write for &'_ mut [u8] {
         let len = self.len().min(buf.len());
         let (head, tail) = core::mem::take(self).split_at_mut(len);
         *self = tail;
-        head.copy_from_slice(buf);
+        head.copy_from_slice(&buf[..len]);
         Ok(len)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            written += self.write(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
     fn flush(&mut self) -> Result<()> {
         Ok(())
     }
@@ -41,12 +81,123 @@ impl super::Write for Vec<u8> {
         Ok(buf.len())
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.reserve(len);
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl super::Read for VecDeque<u8> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let (front, back) = self.as_slices();
+        let n = front.len().min(buf.len());
+        buf[..n].copy_from_slice(&front[..n]);
+        let m = back.len().min(buf.len() - n);
+        buf[n..][..m].copy_from_slice(&back[..m]);
+        self.drain(..n + m);
+        Ok(n + m)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let (front, back) = self.as_slices();
+        let n = front.len().min(cursor.capacity());
+        cursor.append(&front[..n]);
+        let m = back.len().min(cursor.capacity());
+        cursor.append(&back[..m]);
+        self.drain(..n + m);
+        Ok(())
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        let len = self.len();
+        buf.reserve(len);
+        buf.extend(self.drain(..));
+        Ok(len)
+    }
+
+    fn read_to_end_bounded(&mut self, buf: &mut Vec<u8>, max: usize) -> Result<usize> {
+        buf_read_to_end_bounded(self, buf, max)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl super::BufRead for VecDeque<u8> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        // The front is only empty if the whole queue is.
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.len());
+        self.drain(..amt);
+    }
+}
+
+impl super::Write for VecDeque<u8> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.reserve(len);
+        for buf in bufs {
+            self.extend(&**buf);
+        }
+        Ok(len)
+    }
+
     fn flush(&mut self) -> Result<()> {
         Ok(())
     }
 }
 
 pub(crate) fn read_to_end<R: super::Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
+    read_to_end_bounded(r, buf, None)
+}
+
+pub(crate) fn read_to_end_bounded<R: super::Read + ?Sized>(
+    r: &mut R,
+    buf: &mut Vec<u8>,
+    max: Option<usize>,
+) -> Result<usize> {
+    read_to_end_checked(r, buf, max, |r| {
+        // Without a buffer, the only way to find more data is to read it.
+        let mut probe = [0u8; 1];
+        r.read(&mut probe).map(|n| n > 0)
+    })
+}
+
+/// Like `read_to_end_bounded`, but checking for data beyond the limit without consuming it.
+pub(crate) fn buf_read_to_end_bounded<R: super::BufRead + ?Sized>(
+    r: &mut R,
+    buf: &mut Vec<u8>,
+    max: usize,
+) -> Result<usize> {
+    read_to_end_checked(r, buf, Some(max), |r| {
+        r.fill_buf().map(|data| !data.is_empty())
+    })
+}
+
+/// Read to the end, calling `has_more` to find out if the stream continues beyond `max` bytes.
+fn read_to_end_checked<R: super::Read + ?Sized>(
+    r: &mut R,
+    buf: &mut Vec<u8>,
+    max: Option<usize>,
+    mut has_more: impl FnMut(&mut R) -> Result<bool>,
+) -> Result<usize> {
     struct Guard<'vec> {
         buf: &'vec mut Vec<u8>,
         len: usize,
@@ -58,33 +209,77 @@ pub(crate) fn read_to_end<R: super::Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>)
         }
     }
 
+    const TOO_LARGE: super::Error =
+        super::Error::new_static(ErrorKind::FileTooLarge, "stream exceeds the size limit");
+    // The hint is only a guess, `Repeat` for example claims to have `usize::MAX` bytes.
+    const MAX_HINT: usize = 64 * 1024;
+
     let mut guard = Guard {
         len: buf.len(),
         buf,
     };
     let start_len = guard.len;
+    let (hint, _) = r.size_hint();
+    guard
+        .buf
+        .reserve(max.map_or(hint, |max| hint.min(max)).min(MAX_HINT));
+    // The number of initialized bytes in the spare capacity, carried over between reads.
+    let mut initialized = 0;
 
     loop {
-        // Ensure room.
-        if guard.buf.len() == guard.len {
-            guard.buf.reserve(32);
-            guard.buf.resize(guard.buf.capacity(), 0);
-            // FIXME: once it's sound, use `initializer`.
-        }
-
-        let buf = &mut guard.buf[guard.len..];
-        match r.read(buf) {
-            Ok(0) => return Ok(guard.len - start_len),
-            Ok(n) => {
-                assert!(n <= buf.len());
-                guard.len += n;
+        let len = guard.len;
+        let remaining = max.map_or(usize::MAX, |max| max - (len - start_len));
+
+        if remaining == 0 {
+            return match has_more(r) {
+                Ok(false) => Ok(len - start_len),
+                Ok(true) => Err(TOO_LARGE),
+                Err(e) if e.is_interrupted() => continue,
+                Err(e) => Err(e),
+            };
+        }
+
+        // Ensure room, growing in the usual doubling steps but never beyond the limit.
+        if len == guard.buf.capacity() {
+            guard.buf.reserve_exact(len.max(32).min(remaining));
+        }
+
+        let spare = spare_capacity(guard.buf);
+        let spare_len = spare.len().min(remaining);
+        let mut read_buf = BorrowedBuf::from(&mut spare[..spare_len]);
+        // SAFETY: these bytes were initialized by a previous read.
+        unsafe { read_buf.set_init(initialized.min(spare_len)) };
+
+        match r.read_buf(read_buf.unfilled()) {
+            Ok(()) => {}
+            Err(e) if e.is_interrupted() => {
+                initialized = read_buf.init_len();
+                continue;
             }
-            Err(e) if e.is_interrupted() => {}
             Err(e) => return Err(e),
         }
+
+        let filled = read_buf.len();
+        if filled == 0 {
+            return Ok(len - start_len);
+        }
+
+        initialized = read_buf.init_len() - filled;
+        guard.len += filled;
+        // SAFETY: the borrowed buffer guarantees its filled part is initialized.
+        unsafe { guard.buf.set_len(guard.len) };
     }
 }
 
+/// The uninitialized part of the vector, as `Vec::spare_capacity_mut` that is not available with
+/// our minimum supported version.
+fn spare_capacity(buf: &mut Vec<u8>) -> &mut [MaybeUninit<u8>] {
+    let len = buf.len();
+    let spare = buf.capacity() - len;
+    // SAFETY: the vector owns this allocation of `capacity` bytes, the tail is never aliased.
+    unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr().add(len).cast(), spare) }
+}
+
 pub(crate) fn read_to_string<R: super::Read + ?Sized>(
     r: &mut R,
     buf: &mut String,
@@ -92,6 +287,14 @@ pub(crate) fn read_to_string<R: super::Read + ?Sized>(
     append_to_string(r, buf, |r, buf| read_to_end(r, buf))
 }
 
+pub(crate) fn read_to_string_bounded<R: super::Read + ?Sized>(
+    r: &mut R,
+    buf: &mut String,
+    max: usize,
+) -> Result<usize> {
+    append_to_string(r, buf, |r, buf| r.read_to_end_bounded(buf, max))
+}
+
 pub(crate) fn append_to_string<R: super::Read + ?Sized>(
     r: &mut R,
     buf: &mut String,
@@ -102,6 +305,13 @@ pub(crate) fn append_to_string<R: super::Read + ?Sized>(
         len: usize,
     }
 
+    impl Drop for Utf8Guard<'_> {
+        fn drop(&mut self) {
+            // Never leave invalid UTF-8 in the string.
+            self.buf.truncate(self.len)
+        }
+    }
+
     let mut guard = unsafe {
         Utf8Guard {
             len: buf.len(),
@@ -123,33 +333,7 @@ pub(crate) fn read_until<R: super::BufRead + ?Sized>(
     byte: u8,
     buf: &mut Vec<u8>,
 ) -> Result<usize> {
-    let mut read = 0;
-
-    loop {
-        let available = match r.fill_buf() {
-            Ok(n) => n,
-            Err(ref e) if e.is_interrupted() => continue,
-            Err(e) => return Err(e),
-        };
-
-        let (done, used) = match available.iter().position(|&b| b == byte) {
-            Some(n) => {
-                buf.extend_from_slice(&available[..=n]);
-                (true, n + 1)
-            }
-            None => {
-                buf.extend_from_slice(available);
-                (false, available.len())
-            }
-        };
-
-        r.consume(used);
-        read += used;
-
-        if done || used == 0 {
-            return Ok(read);
-        }
-    }
+    super::impls_always::scan_until(r, byte, |chunk| buf.extend_from_slice(chunk))
 }
 
 pub(crate) fn read_line<R: super::BufRead + ?Sized>(r: &mut R, buf: &mut String) -> Result<usize> {
@@ -175,6 +359,28 @@ impl super::Write for super::Cursor<&mut Vec<u8>> {
         vec_write(&mut self.pos, &mut *self.inner, buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> super::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            written += vec_write(&mut self.pos, &mut *self.inner, buf)?;
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> super::Result<()> {
+        Ok(())
+    }
+}
+
+impl super::Write for super::Cursor<Box<[u8]>> {
+    fn write(&mut self, buf: &[u8]) -> super::Result<usize> {
+        super::impls_always::slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> super::Result<usize> {
+        super::impls_always::slice_write_vectored(&mut self.pos, &mut self.inner, bufs)
+    }
+
     fn flush(&mut self) -> super::Result<()> {
         Ok(())
     }
@@ -185,7 +391,219 @@ impl super::Write for super::Cursor<Vec<u8>> {
         vec_write(&mut self.pos, &mut self.inner, buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> super::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            written += vec_write(&mut self.pos, &mut self.inner, buf)?;
+        }
+        Ok(written)
+    }
+
     fn flush(&mut self) -> super::Result<()> {
         Ok(())
     }
 }
+
+impl<B: BufRead> Iterator for Split<B> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Result<Vec<u8>>> {
+        let mut buf = Vec::new();
+        match self.inner.read_until(self.delim, &mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                if buf.last() == Some(&self.delim) {
+                    buf.pop();
+                }
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<B: BufRead> Iterator for Lines<B> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        let mut buf = String::new();
+        match self.inner.read_line(&mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                if buf.ends_with('\n') {
+                    buf.pop();
+                    if buf.ends_with('\r') {
+                        buf.pop();
+                    }
+                }
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<R: Read + ?Sized> Read for Box<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        (**self).read_exact(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        (**self).read_buf(cursor)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    fn read_to_end_bounded(&mut self, buf: &mut Vec<u8>, max: usize) -> Result<usize> {
+        (**self).read_to_end_bounded(buf, max)
+    }
+
+    fn read_to_string_bounded(&mut self, buf: &mut String, max: usize) -> Result<usize> {
+        (**self).read_to_string_bounded(buf, max)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    fn __as_std_read(&mut self, token: crate::sealed::Token) -> Option<crate::__StdRead<'_>> {
+        (**self).__as_std_read(token)
+    }
+}
+
+impl<R: BufRead + ?Sized> BufRead for Box<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line(buf)
+    }
+
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        (**self).skip_until(byte)
+    }
+
+    fn has_data_left(&mut self) -> Result<bool> {
+        (**self).has_data_left()
+    }
+}
+
+impl<S: Seek + ?Sized> Seek for Box<S> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        (**self).seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        (**self).stream_position()
+    }
+
+    fn rewind(&mut self) -> Result<()> {
+        (**self).rewind()
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        (**self).seek_relative(offset)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        (**self).stream_len()
+    }
+}
+
+impl<W: Write + ?Sized> Write for Box<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        (**self).write_all_vectored(bufs)
+    }
+
+    fn write_fmt(&mut self, fmt: core::fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
+    }
+
+    fn __as_std_write(&mut self, token: crate::sealed::Token) -> Option<crate::__StdWrite<'_>> {
+        (**self).__as_std_write(token)
+    }
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.shared.borrow_mut().ring.read(buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.shared.borrow().ring.size_hint()
+    }
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut state = self.shared.borrow_mut();
+        if !state.reader_alive {
+            return Err(super::Error::from(ErrorKind::BrokenPipe));
+        }
+
+        state.ring.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for DuplexStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.reader.read(buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.size_hint()
+    }
+}
+
+impl Write for DuplexStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
@@ -1,5 +1,10 @@
-use super::{BufRead, Cursor, Empty, Read, Repeat, Result, Seek, SeekFrom, Sink, Write};
-use crate::{ErrorKind, Take};
+use super::{
+    BufRead, BufReader, BufWriter, Cursor, Empty, LineWriter, Read, Repeat, Result, Seek, SeekFrom,
+    Sink, Write,
+};
+use crate::{BorrowedCursor, FixedBuf, RingBuffer, SegmentIter, Segments};
+use crate::{Bytes, Chain, Error, ErrorKind, FmtWriter, IoSlice, IoSliceMut, NotIo, Take};
+use crate::{Utf8Writer, WriteLimit};
 
 impl<T> Read for Cursor<T>
 where
@@ -16,6 +21,30 @@ where
         self.consume(buf.len());
         Ok(())
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let read = Read::read_vectored(&mut self.fill_buf()?, bufs)?;
+        self.consume(read);
+        Ok(read)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let start = cursor.written();
+        Read::read_buf(&mut self.fill_buf()?, cursor.reborrow())?;
+        self.consume(cursor.written() - start);
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end_bounded(&mut self, buf: &mut alloc::vec::Vec<u8>, max: usize) -> Result<usize> {
+        crate::impls_alloc::buf_read_to_end_bounded(self, buf, max)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.as_ref().len() as u64;
+        let remaining = len.saturating_sub(self.pos) as usize;
+        (remaining, Some(remaining))
+    }
 }
 
 impl<T> BufRead for Cursor<T>
@@ -32,13 +61,45 @@ where
     }
 }
 
+pub(crate) fn slice_write(pos_mut: &mut u64, slice: &mut [u8], buf: &[u8]) -> Result<usize> {
+    let pos = (*pos_mut).min(slice.len() as u64) as usize;
+    let n = Write::write(&mut &mut slice[pos..], buf)?;
+    *pos_mut += n as u64;
+    Ok(n)
+}
+
+pub(crate) fn slice_write_vectored(
+    pos_mut: &mut u64,
+    slice: &mut [u8],
+    bufs: &[IoSlice<'_>],
+) -> Result<usize> {
+    let pos = (*pos_mut).min(slice.len() as u64) as usize;
+    let n = Write::write_vectored(&mut &mut slice[pos..], bufs)?;
+    *pos_mut += n as u64;
+    Ok(n)
+}
+
 impl Write for Cursor<&mut [u8]> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        let pos = self.pos.min(self.inner.len() as u64) as usize;
-        let ref mut slice = &mut self.inner[pos..];
-        let n = Write::write(slice, buf)?;
-        self.pos += n as u64;
-        Ok(n)
+        slice_write(&mut self.pos, self.inner, buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        slice_write_vectored(&mut self.pos, self.inner, bufs)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> Write for Cursor<[u8; N]> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        slice_write_vectored(&mut self.pos, &mut self.inner, bufs)
     }
 
     fn flush(&mut self) -> Result<()> {
@@ -66,19 +127,32 @@ where
             base_pos.checked_sub(offset.wrapping_neg() as u64)
         };
 
-        self.pos = new_pos.ok_or_else(|| ErrorKind::InvalidInput)?;
+        self.pos = new_pos.ok_or(ErrorKind::InvalidInput)?;
         Ok(self.pos)
     }
 
     fn stream_position(&mut self) -> Result<u64> {
         Ok(self.pos)
     }
+
+    fn rewind(&mut self) -> Result<()> {
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.inner.as_ref().len() as u64)
+    }
 }
 
 impl Read for Empty {
     fn read(&mut self, _: &mut [u8]) -> Result<usize> {
         Ok(0)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
 }
 
 impl BufRead for Empty {
@@ -96,6 +170,15 @@ impl Seek for Empty {
     fn stream_position(&mut self) -> Result<u64> {
         Ok(0)
     }
+    fn rewind(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn seek_relative(&mut self, _: i64) -> Result<()> {
+        Ok(())
+    }
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(0)
+    }
 }
 
 impl Read for Repeat {
@@ -108,6 +191,10 @@ impl Read for Repeat {
         Read::read(self, buf)?;
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
 }
 
 impl Write for Sink {
@@ -130,7 +217,6 @@ impl Write for &'_ Sink {
     }
 }
 
-// FIXME: move to impls_nostd and dispatch to std if available.
 // FIXME: is there any way to do stack probing any other way?
 pub fn stack_copy<R, W>(read: &mut R, write: &mut W) -> Result<u64>
 where
@@ -139,11 +225,26 @@ where
 {
     const DEFAULT_STACK_BUFFER_SIZE: usize = 512;
 
-    let mut written = 0;
     let mut buffer = [0u8; DEFAULT_STACK_BUFFER_SIZE];
+    copy_with_buffer(read, write, &mut buffer)
+}
+
+pub fn copy_with_buffer<R, W>(read: &mut R, write: &mut W, buffer: &mut [u8]) -> Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    if buffer.is_empty() {
+        return Err(Error::new_static(
+            ErrorKind::InvalidInput,
+            "copy requires a non-empty buffer",
+        ));
+    }
+
+    let mut written = 0;
 
     loop {
-        let len = match read.read(&mut buffer[..]) {
+        let len = match read.read(buffer) {
             Ok(0) => return Ok(written),
             Err(ref e) if e.is_interrupted() => continue,
             other => other?,
@@ -154,11 +255,335 @@ where
     }
 }
 
+pub fn copy_buf<R, W>(read: &mut R, write: &mut W) -> Result<u64>
+where
+    R: BufRead + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut written = 0;
+
+    loop {
+        let buffer = match read.fill_buf() {
+            Ok([]) => return Ok(written),
+            Ok(buffer) => buffer,
+            Err(ref e) if e.is_interrupted() => continue,
+            Err(e) => return Err(e),
+        };
+
+        write.write_all(buffer)?;
+        let len = buffer.len();
+        read.consume(len);
+        written += len as u64;
+    }
+}
+
 #[inline(always)]
 fn cap_min(limit: u64, len: usize) -> usize {
     usize::try_from(limit).unwrap_or(len).min(len)
 }
 
+impl<T: Seek> Seek for Take<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Seeking is only allowed within the window of the original limit.
+        let new_pos = match pos {
+            SeekFrom::Start(n) => i128::from(n),
+            SeekFrom::End(n) => i128::from(self.window_len()?) + i128::from(n),
+            SeekFrom::Current(n) => i128::from(self.position()) + i128::from(n),
+        };
+
+        let new_pos = match u64::try_from(new_pos) {
+            Ok(n) if n <= self.len => n,
+            _ => return Err(Error::from(ErrorKind::InvalidInput)),
+        };
+
+        // Move the inner stream with a single seek, the limit only follows once it succeeded.
+        let diff = i128::from(new_pos) - i128::from(self.position());
+        match i64::try_from(diff) {
+            Ok(0) => {}
+            Ok(step) => {
+                self.inner.seek(SeekFrom::Current(step))?;
+            }
+            Err(_) => {
+                let start = self.inner.stream_position()? - self.position();
+                let target = start.checked_add(new_pos).ok_or(ErrorKind::InvalidInput)?;
+                self.inner.seek(SeekFrom::Start(target))?;
+            }
+        }
+
+        self.limit = self.len - new_pos;
+        Ok(new_pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.position())
+    }
+}
+
+impl<T: Seek> Take<T> {
+    /// The length of the window, which ends early if the inner stream is shorter than the limit.
+    fn window_len(&mut self) -> Result<u64> {
+        let pos = self.inner.stream_position()?;
+        let end = self.inner.seek(SeekFrom::End(0))?;
+        self.inner.seek(SeekFrom::Start(pos))?;
+
+        let start = pos.saturating_sub(self.position());
+        Ok(self.len.min(end.saturating_sub(start)))
+    }
+}
+
+impl<W: Write> Write for WriteLimit<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.limit == 0 {
+            return Err(Error::from(ErrorKind::WriteZero));
+        }
+
+        let len = cap_min(self.limit, buf.len());
+        let n = self.inner.write(&buf[..len])?;
+        self.limit = self.limit.saturating_sub(n as u64);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read + ?Sized> Read for &'_ mut R {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        (**self).read_exact(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        (**self).read_buf(cursor)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, buf: &mut alloc::string::String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end_bounded(&mut self, buf: &mut alloc::vec::Vec<u8>, max: usize) -> Result<usize> {
+        (**self).read_to_end_bounded(buf, max)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_string_bounded(
+        &mut self,
+        buf: &mut alloc::string::String,
+        max: usize,
+    ) -> Result<usize> {
+        (**self).read_to_string_bounded(buf, max)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    fn __as_std_read(&mut self, token: crate::sealed::Token) -> Option<crate::__StdRead<'_>> {
+        (**self).__as_std_read(token)
+    }
+}
+
+impl<R: BufRead + ?Sized> BufRead for &'_ mut R {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_until(&mut self, byte: u8, buf: &mut alloc::vec::Vec<u8>) -> Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_line(&mut self, buf: &mut alloc::string::String) -> Result<usize> {
+        (**self).read_line(buf)
+    }
+
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        (**self).skip_until(byte)
+    }
+
+    fn has_data_left(&mut self) -> Result<bool> {
+        (**self).has_data_left()
+    }
+}
+
+impl<S: Seek + ?Sized> Seek for &'_ mut S {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        (**self).seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        (**self).stream_position()
+    }
+
+    fn rewind(&mut self) -> Result<()> {
+        (**self).rewind()
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        (**self).seek_relative(offset)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        (**self).stream_len()
+    }
+}
+
+impl<W: Write + ?Sized> Write for &'_ mut W {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        (**self).write_all_vectored(bufs)
+    }
+
+    fn write_fmt(&mut self, fmt: core::fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
+    }
+
+    fn __as_std_write(&mut self, token: crate::sealed::Token) -> Option<crate::__StdWrite<'_>> {
+        (**self).__as_std_write(token)
+    }
+}
+
+/// Consume bytes up to and including `byte`, passing each consumed chunk to `sink`.
+pub(crate) fn scan_until<R: BufRead + ?Sized>(
+    r: &mut R,
+    byte: u8,
+    mut sink: impl FnMut(&[u8]),
+) -> Result<usize> {
+    let mut read = 0;
+
+    loop {
+        let available = match r.fill_buf() {
+            Ok(n) => n,
+            Err(ref e) if e.is_interrupted() => continue,
+            Err(e) => return Err(e),
+        };
+
+        let (done, used) = match available.iter().position(|&b| b == byte) {
+            Some(n) => (true, n + 1),
+            None => (false, available.len()),
+        };
+
+        sink(&available[..used]);
+        r.consume(used);
+        read += used;
+
+        if done || used == 0 {
+            return Ok(read);
+        }
+    }
+}
+
+impl<R: Read> Iterator for Bytes<R> {
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Result<u8>> {
+        let mut byte = 0;
+        loop {
+            return match self.inner.read(core::slice::from_mut(&mut byte)) {
+                Ok(0) => None,
+                Ok(_) => Some(Ok(byte)),
+                Err(ref e) if e.is_interrupted() => continue,
+                Err(e) => Some(Err(e)),
+            };
+        }
+    }
+}
+
+impl<T: Read, U: Read> Read for Chain<T, U> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if !self.done_first {
+            match self.first.read(buf)? {
+                0 if !buf.is_empty() => self.done_first = true,
+                n => return Ok(n),
+            }
+        }
+        self.second.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        if !self.done_first {
+            match self.first.read_vectored(bufs)? {
+                0 if bufs.iter().any(|b| !b.is_empty()) => self.done_first = true,
+                n => return Ok(n),
+            }
+        }
+        self.second.read_vectored(bufs)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (second_low, second_high) = self.second.size_hint();
+        if self.done_first {
+            return (second_low, second_high);
+        }
+
+        let (first_low, first_high) = self.first.size_hint();
+        let high = match (first_high, second_high) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (first_low.saturating_add(second_low), high)
+    }
+}
+
+impl<T: BufRead, U: BufRead> BufRead for Chain<T, U> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if !self.done_first {
+            match self.first.fill_buf()? {
+                [] => self.done_first = true,
+                buf => return Ok(buf),
+            }
+        }
+        self.second.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if !self.done_first {
+            self.first.consume(amt)
+        } else {
+            self.second.consume(amt)
+        }
+    }
+}
+
 // FIXME: in std this specializes `read_to_end` which would be done in impls_alloc.
 impl<R: Read> Read for Take<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
@@ -172,6 +597,13 @@ impl<R: Read> Read for Take<R> {
         self.limit = self.limit.saturating_sub(n as u64);
         Ok(n)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.inner.size_hint();
+        let limit = cap_min(self.limit, usize::MAX);
+        let high = high.map_or(limit, |high| high.min(limit));
+        (low.min(limit), Some(high))
+    }
 }
 
 impl<T: BufRead> BufRead for Take<T> {
@@ -193,3 +625,649 @@ impl<T: BufRead> BufRead for Take<T> {
         self.inner.consume(amt);
     }
 }
+
+impl<R: Read, B> Read for BufReader<R, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // Bypass our own buffer entirely if it is empty and the request would not fit anyways.
+        if self.pos == self.filled && buf.len() >= self.capacity() {
+            self.discard_buffer();
+            return self.inner.read(buf);
+        }
+
+        let n = Read::read(&mut self.fill_buf()?, buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end_bounded(&mut self, buf: &mut alloc::vec::Vec<u8>, max: usize) -> Result<usize> {
+        crate::impls_alloc::buf_read_to_end_bounded(self, buf, max)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.filled - self.pos;
+        let (low, high) = self.inner.size_hint();
+        (
+            low.saturating_add(buffered),
+            high.and_then(|high| high.checked_add(buffered)),
+        )
+    }
+}
+
+impl<R: Read, B> BufRead for BufReader<R, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.pos >= self.filled {
+            let n = self.inner.read(self.buf.as_mut())?;
+            self.pos = 0;
+            self.filled = n;
+        }
+
+        Ok(self.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+    }
+}
+
+impl<R: Seek, B> Seek for BufReader<R, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let result;
+        if let SeekFrom::Current(n) = pos {
+            // The inner reader is ahead of us by the buffered amount.
+            let remainder = (self.filled - self.pos) as i64;
+            if let Some(offset) = n.checked_sub(remainder) {
+                result = self.inner.seek(SeekFrom::Current(offset))?;
+            } else {
+                // Seek in two steps, the remainder is never negative.
+                self.inner.seek(SeekFrom::Current(-remainder))?;
+                self.discard_buffer();
+                result = self.inner.seek(SeekFrom::Current(n))?;
+            }
+        } else {
+            result = self.inner.seek(pos)?;
+        }
+
+        self.discard_buffer();
+        Ok(result)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        let remainder = (self.filled - self.pos) as u64;
+        let position = self.inner.stream_position()?;
+        Ok(position
+            .checked_sub(remainder)
+            .expect("overflow when subtracting remaining buffer size from inner stream position"))
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        // Stay within the buffer if possible, without touching the inner reader.
+        let pos = self.pos as i64;
+        match pos.checked_add(offset) {
+            Some(new_pos) if new_pos >= 0 && new_pos <= self.filled as i64 => {
+                self.pos = new_pos as usize;
+                Ok(())
+            }
+            _ => self.seek(SeekFrom::Current(offset)).map(drop),
+        }
+    }
+}
+
+impl<W: Write, B> Write for BufWriter<W, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.len + buf.len() > self.capacity() {
+            self.flush_buf()?;
+        }
+
+        // Bypass our own buffer entirely if the data would not fit anyways.
+        if buf.len() >= self.capacity() {
+            self.write_unbuffered(buf)
+        } else {
+            Ok(self.write_to_buf(buf))
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        if self.len + buf.len() > self.capacity() {
+            self.flush_buf()?;
+        }
+
+        if buf.len() >= self.capacity() {
+            self.write_all_unbuffered(buf)
+        } else {
+            self.write_to_buf(buf);
+            Ok(())
+        }
+    }
+}
+
+impl<W: Write + Seek, B> Seek for BufWriter<W, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.flush_buf()?;
+        self.inner.seek(pos)
+    }
+}
+
+impl<W: Write, B> Write for LineWriter<W, B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let newline_idx = match buf.iter().rposition(|&b| b == b'\n') {
+            Some(idx) => idx + 1,
+            None => {
+                // A completed line that is still buffered must be written before new data.
+                if let Some(b'\n') = self.inner.buffer().last() {
+                    self.inner.flush_buf()?;
+                }
+
+                return self.inner.write(buf);
+            }
+        };
+
+        // Write all completed lines directly, after any previous data.
+        self.inner.flush_buf()?;
+        let flushed = self.inner.write_unbuffered(&buf[..newline_idx])?;
+        if flushed == 0 {
+            return Ok(0);
+        }
+
+        // Buffer as much of the rest as possible, without ever buffering a partial line when we
+        // could have buffered a complete one instead.
+        let tail = if flushed >= newline_idx {
+            &buf[flushed..]
+        } else if newline_idx - flushed <= self.inner.capacity() {
+            &buf[flushed..newline_idx]
+        } else {
+            let scan_area = &buf[flushed..][..self.inner.capacity()];
+            match scan_area.iter().rposition(|&b| b == b'\n') {
+                Some(idx) => &scan_area[..idx + 1],
+                None => scan_area,
+            }
+        };
+
+        let buffered = self.inner.write_to_buf(tail);
+        Ok(flushed + buffered)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        let (lines, tail) = match buf.iter().rposition(|&b| b == b'\n') {
+            Some(idx) => buf.split_at(idx + 1),
+            None => {
+                if let Some(b'\n') = self.inner.buffer().last() {
+                    self.inner.flush_buf()?;
+                }
+
+                return self.inner.write_all(buf);
+            }
+        };
+
+        if self.inner.buffer().is_empty() {
+            self.inner.write_all_unbuffered(lines)?;
+        } else {
+            self.inner.write_all(lines)?;
+            self.inner.flush_buf()?;
+        }
+
+        self.inner.write_all(tail)
+    }
+}
+
+pub(crate) fn write_fmt<W: Write + ?Sized>(w: &mut W, fmt: core::fmt::Arguments<'_>) -> Result<()> {
+    let mut output = FmtWriter::new(w);
+
+    match core::fmt::write(&mut output, fmt) {
+        Ok(()) => Ok(()),
+        // A formatting trait implementation failed on its own.
+        Err(_) => Err(output
+            .error
+            .unwrap_or_else(|| Error::from(ErrorKind::Other))),
+    }
+}
+
+impl<W: Write> core::fmt::Write for FmtWriter<W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        fmt_write_str(&mut self.inner, &mut self.error, s)
+    }
+}
+
+fn fmt_write_str<W: Write + ?Sized>(
+    w: &mut W,
+    error: &mut Option<Error>,
+    s: &str,
+) -> core::fmt::Result {
+    match w.write_all(s.as_bytes()) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            // Only the first error caused formatting to stop.
+            error.get_or_insert(err);
+            Err(core::fmt::Error)
+        }
+    }
+}
+
+impl<F: core::fmt::Write> Write for Utf8Writer<F> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut consumed = 0;
+
+        // Complete the character from a previous write first.
+        if self.partial_len > 0 {
+            let have = self.partial_len;
+            let take = (4 - have).min(buf.len());
+            let mut char_buf = self.partial;
+            char_buf[have..have + take].copy_from_slice(&buf[..take]);
+
+            let valid = match core::str::from_utf8(&char_buf[..have + take]) {
+                Ok(_) => have + take,
+                Err(err) if err.valid_up_to() >= have => err.valid_up_to(),
+                Err(err) if err.error_len().is_none() => {
+                    // Still incomplete, we must have consumed all of `buf`.
+                    self.partial = char_buf;
+                    self.partial_len = have + take;
+                    return Ok(take);
+                }
+                Err(_) => return Err(Error::from(ErrorKind::InvalidData)),
+            };
+
+            utf8_write_str(&mut self.inner, &char_buf[..valid])?;
+            self.partial_len = 0;
+            consumed = valid - have;
+        }
+
+        let rest = &buf[consumed..];
+        let (valid, err) = match core::str::from_utf8(rest) {
+            Ok(_) => (rest.len(), None),
+            Err(err) => (err.valid_up_to(), Some(err)),
+        };
+
+        utf8_write_str(&mut self.inner, &rest[..valid])?;
+
+        match err {
+            None => Ok(buf.len()),
+            Some(err) if err.error_len().is_none() => {
+                let tail = &rest[valid..];
+                self.partial[..tail.len()].copy_from_slice(tail);
+                self.partial_len = tail.len();
+                Ok(buf.len())
+            }
+            // Report the error on the next call instead.
+            Some(_) if consumed + valid > 0 => Ok(consumed + valid),
+            Some(_) => Err(Error::from(ErrorKind::InvalidData)),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn utf8_write_str<F: core::fmt::Write>(f: &mut F, valid: &[u8]) -> Result<()> {
+    // SAFETY: the caller validated the bytes with `from_utf8`.
+    let s = unsafe { core::str::from_utf8_unchecked(valid) };
+    f.write_str(s).map_err(|_| Error::from(ErrorKind::Other))
+}
+
+impl<R: Read> Read for NotIo<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.0.read_exact(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        self.0.read_buf(cursor)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn __as_std_read(&mut self, token: crate::sealed::Token) -> Option<crate::__StdRead<'_>> {
+        self.0.__as_std_read(token)
+    }
+}
+
+impl<R: BufRead> BufRead for NotIo<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+}
+
+impl<W: Write> Write for NotIo<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.0.flush()
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.0.write_all(buf)
+    }
+
+    fn __as_std_write(&mut self, token: crate::sealed::Token) -> Option<crate::__StdWrite<'_>> {
+        self.0.__as_std_write(token)
+    }
+}
+
+impl<S: Seek> Seek for NotIo<S> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.0.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.0.stream_position()
+    }
+
+    fn rewind(&mut self) -> Result<()> {
+        self.0.rewind()
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        self.0.seek_relative(offset)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        self.0.stream_len()
+    }
+}
+
+impl<const N: usize> Read for FixedBuf<N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = Read::read(&mut self.fill_buf()?, buf)?;
+        self.consume(read);
+        Ok(read)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let start = cursor.written();
+        Read::read_buf(&mut self.fill_buf()?, cursor.reborrow())?;
+        self.consume(cursor.written() - start);
+        Ok(())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.pos, Some(self.len - self.pos))
+    }
+}
+
+impl<const N: usize> BufRead for FixedBuf<N> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = self.len.min(self.pos + amt);
+    }
+}
+
+impl<const N: usize> Seek for FixedBuf<N> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // The read position is only valid within the written data.
+        let new_pos = match pos {
+            SeekFrom::Start(n) => i128::from(n),
+            SeekFrom::End(n) => self.len as i128 + i128::from(n),
+            SeekFrom::Current(n) => self.pos as i128 + i128::from(n),
+        };
+
+        match usize::try_from(new_pos) {
+            Ok(n) if n <= self.len => self.pos = n,
+            _ => return Err(Error::from(ErrorKind::InvalidInput)),
+        }
+
+        Ok(self.pos as u64)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos as u64)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.len as u64)
+    }
+}
+
+impl<const N: usize> Write for FixedBuf<N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let n = Write::write(&mut &mut self.buf[self.len..], buf)?;
+        if n == 0 {
+            return Err(Error::from(ErrorKind::WriteZero));
+        }
+
+        self.len += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Read from buffered segments until `buf` is full, crossing segment boundaries.
+fn read_segments<R: BufRead + ?Sized>(r: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        let n = Read::read(&mut r.fill_buf()?, &mut buf[read..])?;
+        if n == 0 {
+            break;
+        }
+        r.consume(n);
+        read += n;
+    }
+    Ok(read)
+}
+
+impl Read for Segments<'_, '_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        read_segments(self, buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = cap_min(self.len.saturating_sub(self.pos), usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+impl BufRead for Segments<'_, '_> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        while let Some(segment) = self.segments.get(self.index) {
+            if self.offset < segment.len() {
+                return Ok(&segment[self.offset..]);
+            }
+            self.index += 1;
+            self.offset = 0;
+        }
+
+        Ok(&[])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let available = self.fill_buf().map_or(0, |buf| buf.len());
+        let amt = amt.min(available);
+        self.offset += amt;
+        self.pos += amt as u64;
+    }
+}
+
+impl Seek for Segments<'_, '_> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(n) => i128::from(n),
+            SeekFrom::End(n) => i128::from(self.len) + i128::from(n),
+            SeekFrom::Current(n) => i128::from(self.pos) + i128::from(n),
+        };
+
+        let new_pos = u64::try_from(new_pos).map_err(|_| ErrorKind::InvalidInput)?;
+        self.set_position(new_pos);
+        Ok(new_pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.len)
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> Read for SegmentIter<'a, I> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        read_segments(self, buf)
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> BufRead for SegmentIter<'a, I> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        while self.current.is_empty() {
+            match self.iter.next() {
+                Some(segment) => self.current = segment,
+                None => break,
+            }
+        }
+
+        Ok(self.current)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.current.len());
+        self.current = &self.current[amt..];
+    }
+}
+
+impl<B> Read for RingBuffer<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.len == 0 && !self.closed {
+            return Err(Error::from(ErrorKind::WouldBlock));
+        }
+
+        let (mut first, mut second) = self.as_slices();
+        let mut n = Read::read(&mut first, buf)?;
+        n += Read::read(&mut second, &mut buf[n..])?;
+        self.consume(n);
+        Ok(n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let high = if self.closed { Some(self.len) } else { None };
+        (self.len, high)
+    }
+}
+
+impl<B> BufRead for RingBuffer<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.len == 0 && !self.closed {
+            return Err(Error::from(ErrorKind::WouldBlock));
+        }
+
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.len);
+        self.len -= amt;
+        self.head += amt;
+
+        if self.len == 0 {
+            // Keep the data contiguous for as long as possible.
+            self.head = 0;
+        } else if self.head >= self.capacity() {
+            self.head -= self.capacity();
+        }
+    }
+}
+
+impl<B> Write for RingBuffer<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.closed {
+            return Err(Error::from(ErrorKind::BrokenPipe));
+        }
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.is_full() {
+            return Err(Error::from(ErrorKind::WouldBlock));
+        }
+
+        let capacity = self.capacity();
+        let tail = self.head + self.len;
+        let storage = self.buf.as_mut();
+        // The free space, in up to two parts around the end of the storage.
+        let (first, second) = if tail < capacity {
+            let (before, after) = storage.split_at_mut(tail);
+            (after, &mut before[..self.head])
+        } else {
+            (&mut storage[tail - capacity..self.head], &mut [][..])
+        };
+
+        let mut n = Write::write(&mut &mut *first, buf)?;
+        if n == first.len() {
+            n += Write::write(&mut &mut *second, &buf[n..])?;
+        }
+
+        self.len += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use super::{ErrorKind, SeekFrom};
+use std::io;
+
+pub(crate) fn to_io_kind(kind: ErrorKind) -> io::ErrorKind {
+    use super::ErrorKind::*;
+    match kind {
+        NotFound => io::ErrorKind::NotFound,
+        PermissionDenied => io::ErrorKind::PermissionDenied,
+        AlreadyExists => io::ErrorKind::AlreadyExists,
+        BrokenPipe => io::ErrorKind::BrokenPipe,
+        TimedOut => io::ErrorKind::TimedOut,
+        WriteZero => io::ErrorKind::WriteZero,
+        UnexpectedEof => io::ErrorKind::UnexpectedEof,
+        Interrupted => io::ErrorKind::Interrupted,
+        WouldBlock => io::ErrorKind::WouldBlock,
+        InvalidData => io::ErrorKind::InvalidData,
+        InvalidInput => io::ErrorKind::InvalidInput,
+        Unsupported => io::ErrorKind::Unsupported,
+        OutOfMemory => io::ErrorKind::OutOfMemory,
+        FileTooLarge => io::ErrorKind::Other,
+        BufferFull => io::ErrorKind::Other,
+        Other => io::ErrorKind::Other,
+        #[cfg(feature = "compat")]
+        __Nonexhaustive(never) => match never {},
+    }
+}
+
+pub(crate) fn from_io_kind(kind: io::ErrorKind) -> ErrorKind {
+    use super::ErrorKind::*;
+    match kind {
+        io::ErrorKind::NotFound => NotFound,
+        io::ErrorKind::PermissionDenied => PermissionDenied,
+        io::ErrorKind::AlreadyExists => AlreadyExists,
+        io::ErrorKind::BrokenPipe => BrokenPipe,
+        io::ErrorKind::TimedOut => TimedOut,
+        io::ErrorKind::WriteZero => WriteZero,
+        io::ErrorKind::UnexpectedEof => UnexpectedEof,
+        io::ErrorKind::Interrupted => Interrupted,
+        io::ErrorKind::WouldBlock => WouldBlock,
+        io::ErrorKind::InvalidData => InvalidData,
+        io::ErrorKind::InvalidInput => InvalidInput,
+        io::ErrorKind::Unsupported => Unsupported,
+        io::ErrorKind::OutOfMemory => OutOfMemory,
+        _ => Other,
+    }
+}
+
+impl From<SeekFrom> for io::SeekFrom {
+    fn from(pos: SeekFrom) -> io::SeekFrom {
+        match pos {
+            SeekFrom::Start(n) => io::SeekFrom::Start(n),
+            SeekFrom::End(n) => io::SeekFrom::End(n),
+            SeekFrom::Current(n) => io::SeekFrom::Current(n),
+        }
+    }
+}
+
+impl From<io::SeekFrom> for SeekFrom {
+    fn from(pos: io::SeekFrom) -> SeekFrom {
+        match pos {
+            io::SeekFrom::Start(n) => SeekFrom::Start(n),
+            io::SeekFrom::End(n) => SeekFrom::End(n),
+            io::SeekFrom::Current(n) => SeekFrom::Current(n),
+        }
+    }
+}
@@ -1,18 +1,177 @@
-impl core::fmt::Debug for super::ErrorInner {
+use super::{
+    AllowStd, BorrowedCursor, BufRead, Cursor, ErrorInner, ErrorKind, Read, Result, Seek, SeekFrom,
+};
+use super::{IoSlice, IoSliceMut, StaticError, Write};
+
+impl core::fmt::Debug for ErrorInner {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        let super::ErrorInner::Kind(inner) = self;
-        core::fmt::Debug::fmt(inner, f)
+        match self {
+            ErrorInner::Kind(inner) => core::fmt::Debug::fmt(inner, f),
+            ErrorInner::Static(inner) => core::fmt::Debug::fmt(inner, f),
+        }
+    }
+}
+
+impl ErrorInner {
+    pub(crate) fn fmt_display(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ErrorInner::Kind(inner) => core::fmt::Display::fmt(inner, f),
+            ErrorInner::Static(inner) => core::fmt::Display::fmt(inner, f),
+        }
+    }
+}
+
+#[cfg(all(feature = "core_error", not(feature = "compat")))]
+impl core::error::Error for super::Error {}
+
+#[cfg(all(feature = "core_error", not(feature = "compat")))]
+impl<W: core::fmt::Debug> core::error::Error for super::IntoInnerError<W> {}
+
+#[cfg(feature = "compat")]
+mod compat {
+    use super::super::impls_compat::{from_io_kind, to_io_kind};
+    use super::super::{Error, ErrorInner, StaticError};
+    use std::io;
+
+    impl std::error::Error for Error {}
+
+    impl<W: core::fmt::Debug> std::error::Error for super::super::IntoInnerError<W> {}
+
+    impl std::error::Error for StaticError {}
+
+    impl From<io::Error> for Error {
+        fn from(err: io::Error) -> Error {
+            if let Some(err) = err.get_ref().and_then(|e| e.downcast_ref::<StaticError>()) {
+                return Error {
+                    inner: ErrorInner::Static(*err),
+                };
+            }
+
+            // Without `std` only the kind can be preserved.
+            Error::from(from_io_kind(err.kind()))
+        }
+    }
+
+    impl From<Error> for io::Error {
+        fn from(err: Error) -> io::Error {
+            match err.inner {
+                ErrorInner::Kind(kind) => io::Error::from(to_io_kind(kind)),
+                ErrorInner::Static(err) => io::Error::new(to_io_kind(err.kind), err),
+            }
+        }
     }
 }
 
 impl super::Error {
+    pub(crate) fn kind_impl(&self) -> ErrorKind {
+        match self.inner {
+            ErrorInner::Kind(kind) => kind,
+            ErrorInner::Static(err) => err.kind,
+        }
+    }
+
     pub(crate) fn is_interrupted_impl(&self) -> bool {
-        false
+        self.kind_impl() == ErrorKind::Interrupted
     }
 
-    pub(crate) fn from_kind_impl(kind: super::ErrorKind) -> Self {
+    pub(crate) fn from_kind_impl(kind: ErrorKind) -> Self {
         super::Error {
-            inner: super::ErrorInner::Kind(kind),
+            inner: ErrorInner::Kind(kind),
         }
     }
+
+    pub(crate) fn static_error(&self) -> Option<&StaticError> {
+        match &self.inner {
+            ErrorInner::Static(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl BufRead for AllowStd<&'_ [u8]> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+}
+
+impl<T: AsRef<[u8]>> Read for AllowStd<Cursor<T>> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.0.read_exact(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        self.0.read_buf(cursor)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: AsRef<[u8]>> BufRead for AllowStd<Cursor<T>> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+}
+
+impl<T: AsRef<[u8]>> Seek for AllowStd<Cursor<T>> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.0.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.0.stream_position()
+    }
+
+    fn rewind(&mut self) -> Result<()> {
+        self.0.rewind()
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        self.0.seek_relative(offset)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        self.0.stream_len()
+    }
+}
+
+impl Write for AllowStd<Cursor<&'_ mut [u8]>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<const N: usize> Write for AllowStd<Cursor<[u8; N]>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.0.flush()
+    }
 }
@@ -1,10 +1,22 @@
-use super::{AllowStd, Result};
-use crate::alloc::vec::Vec;
+use super::{AllowStd, BorrowedCursor, Cursor, IoSlice, IoSliceMut, Result};
+use crate::alloc::{boxed::Box, vec::Vec};
 
 impl super::Read for AllowStd<&'_ [u8]> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         self.0.read(buf)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        self.0.read_buf(cursor)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
 impl super::Write for AllowStd<&'_ mut [u8]> {
@@ -12,6 +24,10 @@ impl super::Write for AllowStd<&'_ mut [u8]> {
         self.0.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> Result<()> {
         self.0.flush()
     }
@@ -22,6 +38,52 @@ impl super::Write for AllowStd<Vec<u8>> {
         self.0.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl super::Write for AllowStd<Cursor<Vec<u8>>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl super::Write for AllowStd<Cursor<&'_ mut Vec<u8>>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl super::Write for AllowStd<Cursor<Box<[u8]>>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> Result<()> {
         Ok(())
     }
@@ -1,8 +1,20 @@
-use super::{AllowStd, Result};
+use super::{AllowStd, BorrowedCursor, IoSlice, IoSliceMut, Result};
 impl super::Read for AllowStd<&'_ [u8]> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         self.0.read(buf)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        self.0.read_buf(cursor)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
 impl super::Read for &'_ [u8] {
@@ -12,6 +24,28 @@ impl super::Read for &'_ [u8] {
         *self = &self[len..];
         Ok(len)
     }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let len = self.len().min(cursor.capacity());
+        cursor.append(&self[..len]);
+        *self = &self[len..];
+        Ok(())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let mut read = 0;
+        for buf in bufs {
+            read += self.read(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+        Ok(read)
+    }
 }
 
 impl super::BufRead for &'_ [u8] {
@@ -29,6 +63,10 @@ impl super::Write for AllowStd<&'_ mut [u8]> {
         self.0.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> Result<()> {
         self.0.flush()
     }
@@ -39,10 +77,21 @@ impl super::Write for &'_ mut [u8] {
         let len = self.len().min(buf.len());
         let (head, tail) = core::mem::take(self).split_at_mut(len);
         *self = tail;
-        head.copy_from_slice(buf);
+        head.copy_from_slice(&buf[..len]);
         Ok(len)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            written += self.write(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
     fn flush(&mut self) -> Result<()> {
         Ok(())
     }
@@ -1,4 +1,6 @@
-use super::{AllowStd, Error, ErrorInner, Result};
+use super::impls_compat::{from_io_kind, to_io_kind};
+use super::{AllowStd, Error, ErrorInner, ErrorKind, NotIo, Result, StaticError};
+use super::{BlockingPipeReader, BlockingPipeWriter};
 use std::io;
 use std::io::{IoSlice, IoSliceMut};
 
@@ -6,6 +8,15 @@ impl<R: io::Read> super::Read for AllowStd<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         io::Read::read(&mut self.0, buf).map_err(Error::from)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [super::IoSliceMut<'_>]) -> Result<usize> {
+        let bufs = super::IoSliceMut::as_std(bufs);
+        io::Read::read_vectored(&mut self.0, bufs).map_err(Error::from)
+    }
+
+    fn __as_std_read(&mut self, _: super::sealed::Token) -> Option<&mut dyn io::Read> {
+        Some(&mut self.0)
+    }
 }
 
 impl<R: io::Read> io::Read for AllowStd<R> {
@@ -33,6 +44,14 @@ impl<W: io::Write> super::Write for AllowStd<W> {
     fn flush(&mut self) -> Result<()> {
         io::Write::flush(&mut self.0).map_err(Error::from)
     }
+    fn write_vectored(&mut self, bufs: &[super::IoSlice<'_>]) -> Result<usize> {
+        let bufs = super::IoSlice::as_std(bufs);
+        io::Write::write_vectored(&mut self.0, bufs).map_err(Error::from)
+    }
+
+    fn __as_std_write(&mut self, _: super::sealed::Token) -> Option<&mut dyn io::Write> {
+        Some(&mut self.0)
+    }
 }
 
 impl<W: io::Write> io::Write for AllowStd<W> {
@@ -50,8 +69,235 @@ impl<W: io::Write> io::Write for AllowStd<W> {
     }
 }
 
+impl<R: io::BufRead> super::BufRead for AllowStd<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        io::BufRead::fill_buf(&mut self.0).map_err(Error::from)
+    }
+    fn consume(&mut self, amt: usize) {
+        io::BufRead::consume(&mut self.0, amt)
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        io::BufRead::read_until(&mut self.0, byte, buf).map_err(Error::from)
+    }
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        io::BufRead::read_line(&mut self.0, buf).map_err(Error::from)
+    }
+}
+
+impl<R: io::BufRead> io::BufRead for AllowStd<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.0.read_until(byte, buf)
+    }
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.0.read_line(buf)
+    }
+}
+
+impl<S: io::Seek> super::Seek for AllowStd<S> {
+    fn seek(&mut self, pos: super::SeekFrom) -> Result<u64> {
+        io::Seek::seek(&mut self.0, pos.into()).map_err(Error::from)
+    }
+    fn stream_position(&mut self) -> Result<u64> {
+        io::Seek::stream_position(&mut self.0).map_err(Error::from)
+    }
+    fn rewind(&mut self) -> Result<()> {
+        io::Seek::rewind(&mut self.0).map_err(Error::from)
+    }
+    #[cfg(not_io_seek_relative)]
+    #[allow(clippy::incompatible_msrv)] // Only with Rust 1.80, detected by the build script.
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        io::Seek::seek_relative(&mut self.0, offset).map_err(Error::from)
+    }
+    // `stream_len` of `std::io::Seek` is unstable, the default seeks to the end and back instead.
+}
+
+impl<S: io::Seek> io::Seek for AllowStd<S> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.0.stream_position()
+    }
+    fn rewind(&mut self) -> io::Result<()> {
+        self.0.rewind()
+    }
+    #[cfg(not_io_seek_relative)]
+    #[allow(clippy::incompatible_msrv)] // Only with Rust 1.80, detected by the build script.
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        self.0.seek_relative(offset)
+    }
+    // `stream_len` is unstable in `std`, so it can not be forwarded.
+}
+
+impl<R: super::Read> io::Read for NotIo<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf).map_err(io::Error::from)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let bufs = super::IoSliceMut::from_std(bufs);
+        self.0.read_vectored(bufs).map_err(io::Error::from)
+    }
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.0.read_to_end(buf).map_err(io::Error::from)
+    }
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.0.read_to_string(buf).map_err(io::Error::from)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.0.read_exact(buf).map_err(io::Error::from)
+    }
+}
+
+impl<R: super::BufRead> io::BufRead for NotIo<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.0.fill_buf().map_err(io::Error::from)
+    }
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.0.read_until(byte, buf).map_err(io::Error::from)
+    }
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.0.read_line(buf).map_err(io::Error::from)
+    }
+}
+
+impl<W: super::Write> io::Write for NotIo<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf).map_err(io::Error::from)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush().map_err(io::Error::from)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let bufs = super::IoSlice::from_std(bufs);
+        self.0.write_vectored(bufs).map_err(io::Error::from)
+    }
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.write_all(buf).map_err(io::Error::from)
+    }
+}
+
+impl<S: super::Seek> io::Seek for NotIo<S> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos.into()).map_err(io::Error::from)
+    }
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.0.stream_position().map_err(io::Error::from)
+    }
+    fn rewind(&mut self) -> io::Result<()> {
+        self.0.rewind().map_err(io::Error::from)
+    }
+    #[cfg(not_io_seek_relative)]
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        self.0.seek_relative(offset).map_err(io::Error::from)
+    }
+    // `stream_len` is unstable in `std`, so it can not be forwarded.
+}
+
+/// Makes the generic `AllowStd` impls cover our `Cursor`, as its individual impls do without `std`.
+impl<T: AsRef<[u8]>> io::Read for super::Cursor<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        super::Read::read(self, buf).map_err(io::Error::from)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let bufs = super::IoSliceMut::from_std(bufs);
+        super::Read::read_vectored(self, bufs).map_err(io::Error::from)
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        super::Read::read_exact(self, buf).map_err(io::Error::from)
+    }
+}
+
+impl<T: AsRef<[u8]>> io::BufRead for super::Cursor<T> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        super::BufRead::fill_buf(self).map_err(io::Error::from)
+    }
+    fn consume(&mut self, amt: usize) {
+        super::BufRead::consume(self, amt)
+    }
+}
+
+impl<T: AsRef<[u8]>> io::Seek for super::Cursor<T> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        super::Seek::seek(self, pos.into()).map_err(io::Error::from)
+    }
+    fn stream_position(&mut self) -> io::Result<u64> {
+        super::Seek::stream_position(self).map_err(io::Error::from)
+    }
+    #[cfg(not_io_seek_relative)]
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        super::Seek::seek_relative(self, offset).map_err(io::Error::from)
+    }
+}
+
+impl io::Write for super::Cursor<&'_ mut [u8]> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        super::Write::write(self, buf).map_err(io::Error::from)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> io::Write for super::Cursor<[u8; N]> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        super::Write::write(self, buf).map_err(io::Error::from)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Write for super::Cursor<Box<[u8]>> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        super::Write::write(self, buf).map_err(io::Error::from)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Write for super::Cursor<&'_ mut Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        super::Write::write(self, buf).map_err(io::Error::from)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Write for super::Cursor<Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        super::Write::write(self, buf).map_err(io::Error::from)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Error {
+        // Recover errors that were only converted for passing through `std`.
+        if let Some(err) = err.get_ref().and_then(|e| e.downcast_ref::<StaticError>()) {
+            return Error {
+                inner: ErrorInner::Static(*err),
+            };
+        }
+
+        if let Some(KindError(kind)) = err.get_ref().and_then(|e| e.downcast_ref()) {
+            return Error {
+                inner: ErrorInner::Kind(*kind),
+            };
+        }
+
         Error {
             inner: ErrorInner::Error(err),
         }
@@ -60,35 +306,136 @@ impl From<io::Error> for Error {
 
 impl From<Error> for std::io::Error {
     fn from(err: Error) -> std::io::Error {
-        let ErrorInner::Error(io) = err.inner;
-        io
+        match err.inner {
+            ErrorInner::Kind(kind) => io::Error::new(to_io_kind(kind), KindError(kind)),
+            ErrorInner::Static(err) => io::Error::new(to_io_kind(err.kind), err),
+            ErrorInner::Error(io) => io,
+        }
+    }
+}
+
+/// The payload of converted errors whose kind has no counterpart in `std`.
+#[derive(Debug)]
+struct KindError(ErrorKind);
+
+impl core::fmt::Display for KindError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for KindError {}
+
+impl std::error::Error for StaticError {}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.inner {
+            ErrorInner::Kind(_) | ErrorInner::Static(_) => None,
+            ErrorInner::Error(err) => std::error::Error::source(err),
+        }
     }
 }
 
+impl<W: core::fmt::Debug> std::error::Error for super::IntoInnerError<W> {}
+
 impl core::fmt::Debug for super::ErrorInner {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        let super::ErrorInner::Error(io) = self;
-        core::fmt::Debug::fmt(io, f)
+        match self {
+            ErrorInner::Kind(inner) => core::fmt::Debug::fmt(inner, f),
+            ErrorInner::Static(inner) => core::fmt::Debug::fmt(inner, f),
+            ErrorInner::Error(io) => core::fmt::Debug::fmt(io, f),
+        }
+    }
+}
+
+impl ErrorInner {
+    pub(crate) fn fmt_display(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ErrorInner::Kind(inner) => core::fmt::Display::fmt(inner, f),
+            ErrorInner::Static(inner) => core::fmt::Display::fmt(inner, f),
+            ErrorInner::Error(io) => core::fmt::Display::fmt(io, f),
+        }
     }
 }
 
 impl super::Error {
+    pub(crate) fn kind_impl(&self) -> ErrorKind {
+        match &self.inner {
+            ErrorInner::Kind(kind) => *kind,
+            ErrorInner::Static(err) => err.kind,
+            ErrorInner::Error(err) => from_io_kind(err.kind()),
+        }
+    }
+
     pub(crate) fn is_interrupted_impl(&self) -> bool {
         match &self.inner {
+            ErrorInner::Kind(kind) => *kind == ErrorKind::Interrupted,
+            ErrorInner::Static(err) => err.kind == ErrorKind::Interrupted,
             ErrorInner::Error(err) => err.kind() == io::ErrorKind::Interrupted,
         }
     }
 
-    pub(crate) fn from_kind_impl(kind: super::ErrorKind) -> Self {
-        use super::ErrorKind::*;
-        io::Error::from(match kind {
-            WriteZero => io::ErrorKind::WriteZero,
-            UnexpectedEof => io::ErrorKind::UnexpectedEof,
-            Interrupted => io::ErrorKind::Interrupted,
-            WouldBlock => io::ErrorKind::WouldBlock,
-            InvalidData => io::ErrorKind::InvalidData,
-            InvalidInput => io::ErrorKind::InvalidInput,
-        })
-        .into()
+    pub(crate) fn from_kind_impl(kind: ErrorKind) -> Self {
+        match to_io_kind(kind) {
+            // Keep the kinds that would otherwise be reported as `Other`.
+            io::ErrorKind::Other if kind != ErrorKind::Other => Error {
+                inner: ErrorInner::Kind(kind),
+            },
+            io_kind => io::Error::from(io_kind).into(),
+        }
+    }
+
+    pub(crate) fn static_error(&self) -> Option<&StaticError> {
+        match &self.inner {
+            ErrorInner::Static(err) => Some(err),
+            ErrorInner::Kind(_) | ErrorInner::Error(_) => None,
+        }
+    }
+}
+
+impl super::Read for BlockingPipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut state = self.shared.lock();
+        loop {
+            match super::Read::read(&mut state.ring, buf) {
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    state = self.shared.wait(state);
+                }
+                other => {
+                    self.shared.notify();
+                    return other;
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        super::Read::size_hint(&self.shared.lock().ring)
+    }
+}
+
+impl super::Write for BlockingPipeWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut state = self.shared.lock();
+        loop {
+            if !state.reader_alive {
+                return Err(Error::from(ErrorKind::BrokenPipe));
+            }
+
+            match super::Write::write(&mut state.ring, buf) {
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    state = self.shared.wait(state);
+                }
+                other => {
+                    self.shared.notify();
+                    return other;
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
     }
 }
@@ -0,0 +1,211 @@
+use core::ops::{Deref, DerefMut};
+
+/// A buffer for vectored writes, like `std::io::IoSlice`.
+///
+/// With the `std` feature this wraps the standard type and has the identical layout. In particular
+/// slices of either type can be converted into each other without copying.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct IoSlice<'a>(pub(crate) IoSliceInner<'a>);
+
+/// A buffer for vectored reads, like `std::io::IoSliceMut`.
+///
+/// With the `std` feature this wraps the standard type and has the identical layout.
+#[repr(transparent)]
+pub struct IoSliceMut<'a>(pub(crate) IoSliceMutInner<'a>);
+
+#[cfg(not(feature = "std"))]
+pub(crate) type IoSliceInner<'a> = &'a [u8];
+#[cfg(feature = "std")]
+pub(crate) type IoSliceInner<'a> = std::io::IoSlice<'a>;
+
+#[cfg(not(feature = "std"))]
+pub(crate) type IoSliceMutInner<'a> = &'a mut [u8];
+#[cfg(feature = "std")]
+pub(crate) type IoSliceMutInner<'a> = std::io::IoSliceMut<'a>;
+
+impl<'a> IoSlice<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        #[cfg(not(feature = "std"))]
+        {
+            IoSlice(buf)
+        }
+        #[cfg(feature = "std")]
+        {
+            IoSlice(std::io::IoSlice::new(buf))
+        }
+    }
+
+    /// Skip `n` bytes at the start of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// When `n` is larger than the length of the buffer.
+    pub fn advance(&mut self, n: usize) {
+        let buf = self.as_slice();
+        *self = IoSlice::new(&buf[n..]);
+    }
+
+    /// Skip `n` bytes at the start of a sequence of buffers, removing all that are used up.
+    ///
+    /// # Panics
+    ///
+    /// When `n` is larger than the combined length of all buffers.
+    pub fn advance_slices(bufs: &mut &mut [IoSlice<'a>], n: usize) {
+        let mut remove = 0;
+        let mut left = n;
+        for buf in bufs.iter() {
+            if let Some(remainder) = left.checked_sub(buf.len()) {
+                left = remainder;
+                remove += 1;
+            } else {
+                break;
+            }
+        }
+
+        *bufs = &mut core::mem::take(bufs)[remove..];
+        if bufs.is_empty() {
+            assert!(left == 0, "advancing io slices beyond their length");
+        } else {
+            bufs[0].advance(left);
+        }
+    }
+
+    fn as_slice(&self) -> &'a [u8] {
+        #[cfg(not(feature = "std"))]
+        {
+            self.0
+        }
+        // SAFETY: the standard type guarantees the memory is borrowed for `'a`.
+        #[cfg(feature = "std")]
+        unsafe {
+            core::slice::from_raw_parts(self.0.as_ptr(), self.0.len())
+        }
+    }
+}
+
+impl<'a> IoSliceMut<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        #[cfg(not(feature = "std"))]
+        {
+            IoSliceMut(buf)
+        }
+        #[cfg(feature = "std")]
+        {
+            IoSliceMut(std::io::IoSliceMut::new(buf))
+        }
+    }
+
+    /// Skip `n` bytes at the start of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// When `n` is larger than the length of the buffer.
+    pub fn advance(&mut self, n: usize) {
+        let buf = self.take_slice();
+        *self = IoSliceMut::new(&mut buf[n..]);
+    }
+
+    /// Skip `n` bytes at the start of a sequence of buffers, removing all that are used up.
+    ///
+    /// # Panics
+    ///
+    /// When `n` is larger than the combined length of all buffers.
+    pub fn advance_slices(bufs: &mut &mut [IoSliceMut<'a>], n: usize) {
+        let mut remove = 0;
+        let mut left = n;
+        for buf in bufs.iter() {
+            if let Some(remainder) = left.checked_sub(buf.len()) {
+                left = remainder;
+                remove += 1;
+            } else {
+                break;
+            }
+        }
+
+        *bufs = &mut core::mem::take(bufs)[remove..];
+        if bufs.is_empty() {
+            assert!(left == 0, "advancing io slices beyond their length");
+        } else {
+            bufs[0].advance(left);
+        }
+    }
+
+    /// Move the borrowed buffer out, leaving an empty one.
+    fn take_slice(&mut self) -> &'a mut [u8] {
+        #[cfg(not(feature = "std"))]
+        {
+            core::mem::take(&mut self.0)
+        }
+        #[cfg(feature = "std")]
+        {
+            let (ptr, len) = (self.0.as_mut_ptr(), self.0.len());
+            self.0 = std::io::IoSliceMut::new(&mut []);
+            // SAFETY: the standard type guarantees the memory is uniquely borrowed for `'a` and
+            // we no longer hold any other reference to it.
+            unsafe { core::slice::from_raw_parts_mut(ptr, len) }
+        }
+    }
+}
+
+impl Deref for IoSlice<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Deref for IoSliceMut<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0.deref()
+    }
+}
+
+impl DerefMut for IoSliceMut<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.0.deref_mut()
+    }
+}
+
+impl core::fmt::Debug for IoSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl core::fmt::Debug for IoSliceMut<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> IoSlice<'a> {
+    pub(crate) fn as_std<'s>(bufs: &'s [IoSlice<'a>]) -> &'s [std::io::IoSlice<'a>] {
+        // SAFETY: `IoSlice` is a transparent wrapper.
+        unsafe { &*(bufs as *const [IoSlice<'a>] as *const [std::io::IoSlice<'a>]) }
+    }
+
+    pub(crate) fn from_std<'s>(bufs: &'s [std::io::IoSlice<'a>]) -> &'s [IoSlice<'a>] {
+        // SAFETY: `IoSlice` is a transparent wrapper.
+        unsafe { &*(bufs as *const [std::io::IoSlice<'a>] as *const [IoSlice<'a>]) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> IoSliceMut<'a> {
+    pub(crate) fn as_std<'s>(bufs: &'s mut [IoSliceMut<'a>]) -> &'s mut [std::io::IoSliceMut<'a>] {
+        // SAFETY: `IoSliceMut` is a transparent wrapper.
+        unsafe { &mut *(bufs as *mut [IoSliceMut<'a>] as *mut [std::io::IoSliceMut<'a>]) }
+    }
+
+    pub(crate) fn from_std<'s>(
+        bufs: &'s mut [std::io::IoSliceMut<'a>],
+    ) -> &'s mut [IoSliceMut<'a>] {
+        // SAFETY: `IoSliceMut` is a transparent wrapper.
+        unsafe { &mut *(bufs as *mut [std::io::IoSliceMut<'a>] as *mut [IoSliceMut<'a>]) }
+    }
+}
@@ -105,23 +105,55 @@
 //!
 #![cfg_attr(all(not(feature = "std"), not(feature = "compat")), no_std)]
 
-#[cfg(all(feature = "alloc"))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod borrowed_buf;
+mod buffered;
 mod cursor;
+pub mod delimited;
 mod empty;
+mod fixed_buf;
+mod fmt_adaptor;
+mod io_slice;
+#[cfg(feature = "alloc")]
+mod pipe;
 mod read_adaptor;
+mod ring_buffer;
+mod segments;
+mod write_adaptor;
 
+pub use self::borrowed_buf::{BorrowedBuf, BorrowedCursor};
+pub use self::buffered::{BufReader, BufWriter, IntoInnerError, LineWriter};
 pub use self::cursor::Cursor;
 pub use self::empty::{Empty, Repeat, Sink};
-pub use self::read_adaptor::Take;
+pub use self::fixed_buf::FixedBuf;
+pub use self::fmt_adaptor::{FmtWriter, Utf8Writer};
+pub use self::io_slice::{IoSlice, IoSliceMut};
+#[cfg(feature = "std")]
+pub use self::pipe::{BlockingPipeReader, BlockingPipeWriter};
+#[cfg(feature = "alloc")]
+pub use self::pipe::{DuplexStream, PipeReader, PipeWriter};
+pub use self::read_adaptor::{Bytes, Chain, Take};
+#[cfg(feature = "alloc")]
+pub use self::read_adaptor::{Lines, Split};
+pub use self::ring_buffer::RingBuffer;
+pub use self::segments::{SegmentIter, Segments};
+pub use self::write_adaptor::WriteLimit;
 
 /// An opaque error.
 ///
 /// This is either equivalent to `std::io::Error` when the `std` feature is enabled, or it's a
 /// stripped down version. In any case it is constructible from the non-exhaustive `ErrorKind` that
-/// lists all the simple error conditions that do not depend on OS implementation.
+/// lists all the simple error conditions that do not depend on OS implementation, and that kind
+/// can be inspected with `Error::kind` in all configurations.
+///
+/// The error implements `std::error::Error` with `std` and `core::error::Error` with the
+/// `core_error` feature. Without `std` it is also `Clone` and `PartialEq`, where two errors are
+/// equal if they have the same kind and, for errors created by `new_static`, the same message and
+/// code. With `std` neither is implemented as `std::io::Error` is not.
 #[derive(Debug)]
+#[cfg_attr(not(feature = "std"), derive(Clone, PartialEq))]
 pub struct Error {
     #[allow(dead_code)]
     inner: ErrorInner,
@@ -133,9 +165,19 @@ pub struct Error {
 /// not match. However, it will not have any performance costs as the respective variant is
 /// implemented in such a way that `rustc` is able to prove that it can never be constructed and
 /// hence eliminates all branches matching it.
-#[non_exhaustive]
-#[derive(Debug)]
+#[cfg_attr(not(feature = "compat"), non_exhaustive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ErrorKind {
+    /// An entity, such as a file, was not found.
+    NotFound,
+    /// The operation lacked the necessary privileges.
+    PermissionDenied,
+    /// An entity already exists where a new one should be created.
+    AlreadyExists,
+    /// The other side of a stream or pipe was closed.
+    BrokenPipe,
+    /// The operation did not complete before its deadline.
+    TimedOut,
     /// No bytes of a buffer have been written.
     WriteZero,
     /// No bytes of a buffer have been read.
@@ -149,17 +191,103 @@ pub enum ErrorKind {
     InvalidData,
     /// Argument to an IO function was not valid.
     InvalidInput,
+    /// The operation is not supported by this reader or writer.
+    Unsupported,
+    /// An allocation required by the operation failed.
+    OutOfMemory,
+    /// The data exceeds a size limit, such as the maximum of a bounded read.
+    ///
+    /// With `std` this has no counterpart for our minimum supported Rust version and converts to
+    /// `std::io::ErrorKind::Other`. The kind is preserved when passed through `std` though.
+    FileTooLarge,
+    /// The data does not fit into a buffer provided by the caller.
+    ///
+    /// With `std` this converts to `std::io::ErrorKind::Other` but is preserved like
+    /// `FileTooLarge`.
+    BufferFull,
+    /// Any error not covered by the other kinds.
+    ///
+    /// With `std` this includes all kinds of `std::io::ErrorKind` without a counterpart here.
+    Other,
+    #[cfg(feature = "compat")]
+    #[doc(hidden)]
+    __Nonexhaustive(compat::Never),
+}
+
+#[cfg(feature = "compat")]
+mod compat {
+    /// Uninhabited, such that the variant containing it can not be constructed.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Never {}
 }
 
+#[cfg_attr(not(feature = "std"), derive(Clone, PartialEq))]
 enum ErrorInner {
-    #[cfg(not(feature = "std"))]
+    /// With `std` only used for kinds that `std::io::ErrorKind` can not represent.
     Kind(ErrorKind),
+    Static(StaticError),
     #[cfg(feature = "std")]
     Error(std::io::Error),
 }
 
+/// The payload of errors constructed in `const` context.
+///
+/// With `std` this also becomes the custom error payload of the converted `std::io::Error`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct StaticError {
+    kind: ErrorKind,
+    message: &'static str,
+    code: Option<u32>,
+}
+
 /// Public interface block for `Error`, independent of features.
 impl Error {
+    /// Create an error with a message, without allocating.
+    ///
+    /// When converted to a `std::io::Error` the message is kept as its custom payload.
+    pub const fn new_static(kind: ErrorKind, message: &'static str) -> Self {
+        Error {
+            inner: ErrorInner::Static(StaticError {
+                kind,
+                message,
+                code: None,
+            }),
+        }
+    }
+
+    /// Create an error with a message and an additional numeric code, without allocating.
+    ///
+    /// The meaning of the code is entirely up to the caller.
+    pub const fn new_static_code(kind: ErrorKind, message: &'static str, code: u32) -> Self {
+        Error {
+            inner: ErrorInner::Static(StaticError {
+                kind,
+                message,
+                code: Some(code),
+            }),
+        }
+    }
+
+    /// The message, if this error was constructed with one by `new_static`.
+    pub fn static_message(&self) -> Option<&'static str> {
+        self.static_error().map(|err| err.message)
+    }
+
+    /// The numeric code, if this error was constructed with one by `new_static_code`.
+    pub fn code(&self) -> Option<u32> {
+        self.static_error().and_then(|err| err.code)
+    }
+
+    /// Return the kind of this error.
+    ///
+    /// With `std` enabled this is derived from the kind of the wrapped `std::io::Error`. Kinds
+    /// without a counterpart in `std` are kept as they are, also when converting to and back from
+    /// `std::io::Error`.
+    pub fn kind(&self) -> ErrorKind {
+        // Dispatch to feature combination.
+        self.kind_impl()
+    }
+
     pub(crate) fn is_interrupted(&self) -> bool {
         // Dispatch to feature combination.
         self.is_interrupted_impl()
@@ -171,6 +299,50 @@ impl Error {
     }
 }
 
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Dispatch to feature combination.
+        self.inner.fmt_display(f)
+    }
+}
+
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use ErrorKind::*;
+        // Same wording as `std`.
+        f.write_str(match self {
+            NotFound => "entity not found",
+            PermissionDenied => "permission denied",
+            AlreadyExists => "entity already exists",
+            BrokenPipe => "broken pipe",
+            TimedOut => "timed out",
+            WriteZero => "write zero",
+            UnexpectedEof => "unexpected end of file",
+            Interrupted => "operation interrupted",
+            WouldBlock => "operation would block",
+            InvalidData => "invalid data",
+            InvalidInput => "invalid input parameter",
+            Unsupported => "unsupported",
+            OutOfMemory => "out of memory",
+            FileTooLarge => "file too large",
+            BufferFull => "buffer full",
+            Other => "other error",
+            #[cfg(feature = "compat")]
+            __Nonexhaustive(never) => match *never {},
+        })
+    }
+}
+
+impl core::fmt::Display for StaticError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.message)?;
+        if let Some(code) = self.code {
+            write!(f, " (code {})", code)?;
+        }
+        Ok(())
+    }
+}
+
 impl From<ErrorKind> for Error {
     fn from(err: ErrorKind) -> Self {
         Error::from_kind(err)
@@ -201,6 +373,27 @@ pub trait Read {
         Ok(())
     }
 
+    /// Like `read`, but reading into a sequence of buffers.
+    ///
+    /// The default implementation reads into the first non-empty buffer.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let buf = bufs
+            .iter_mut()
+            .find(|b| !b.is_empty())
+            .map_or(&mut [][..], |b| &mut **b);
+        self.read(buf)
+    }
+
+    /// Read into the unfilled part of a borrowed buffer, which may be uninitialized.
+    ///
+    /// The default implementation initializes the remaining buffer and calls `read`. Readers that
+    /// can copy their data directly should override it, so that the memory is never zeroed.
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let n = self.read(cursor.ensure_init().init_mut())?;
+        cursor.advance(n);
+        Ok(())
+    }
+
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize> {
         impls_alloc::read_to_end(self, buf)
@@ -211,11 +404,87 @@ pub trait Read {
         impls_alloc::read_to_string(self, buf)
     }
 
+    /// Like `read_to_end`, but appending at most `max` bytes.
+    ///
+    /// If the stream has more data, this fails with `ErrorKind::FileTooLarge` and the vector keeps
+    /// the first `max` bytes. Any other error also leaves only the data read so far.
+    ///
+    /// To find out if there is more data, the default reads one byte beyond the limit, which is
+    /// lost then. Buffered readers such as `BufReader`, `Cursor` and byte slices instead look at
+    /// their buffer and leave the data beyond the limit in the reader.
+    #[cfg(feature = "alloc")]
+    fn read_to_end_bounded(&mut self, buf: &mut alloc::vec::Vec<u8>, max: usize) -> Result<usize> {
+        impls_alloc::read_to_end_bounded(self, buf, Some(max))
+    }
+
+    /// Like `read_to_string`, but appending at most `max` bytes.
+    ///
+    /// Fails with `ErrorKind::FileTooLarge` as `read_to_end_bounded`. The string is only extended
+    /// if the data read is valid UTF-8.
+    #[cfg(feature = "alloc")]
+    fn read_to_string_bounded(
+        &mut self,
+        buf: &mut alloc::string::String,
+        max: usize,
+    ) -> Result<usize> {
+        impls_alloc::read_to_string_bounded(self, buf, max)
+    }
+
+    /// Bounds on the number of bytes remaining in the stream, like `Iterator::size_hint`.
+    ///
+    /// The lower bound is used to pre-allocate when reading to the end. The default returns
+    /// `(0, None)`, which is correct for every reader.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    /// The standard reader that is wrapped, for dispatching to `std` in `copy`.
+    ///
+    /// The returned reader must read exactly the data that reading `self` would. This is not
+    /// public API: only this crate can create the token, so the hook can neither be called nor
+    /// overridden elsewhere.
+    #[doc(hidden)]
+    fn __as_std_read(&mut self, _: sealed::Token) -> Option<__StdRead<'_>> {
+        None
+    }
+
+    /// Borrow this reader, such that adaptors can be used without consuming it.
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Turn this reader into an iterator over its bytes.
+    fn bytes(self) -> Bytes<Self>
+    where
+        Self: Sized,
+    {
+        Bytes { inner: self }
+    }
+
+    /// Read from `next` once this reader is at its end.
+    fn chain<R: Read>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+            done_first: false,
+        }
+    }
+
     fn take(self, limit: u64) -> Take<Self>
     where
         Self: Sized,
     {
-        Take { inner: self, limit }
+        Take {
+            inner: self,
+            len: limit,
+            limit,
+        }
     }
 }
 
@@ -233,6 +502,43 @@ pub trait BufRead: Read {
     fn read_line(&mut self, buf: &mut alloc::string::String) -> Result<usize> {
         impls_alloc::read_line(self, buf)
     }
+
+    /// Skip all bytes up to and including the delimiter `byte`, or to the end of the stream.
+    ///
+    /// Returns the number of bytes skipped.
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        impls_always::scan_until(self, byte, |_| {})
+    }
+
+    /// Check if the stream has any data left to be read, filling the buffer if necessary.
+    fn has_data_left(&mut self) -> Result<bool> {
+        self.fill_buf().map(|buf| !buf.is_empty())
+    }
+
+    /// Turn this reader into an iterator over the segments delimited by `byte`.
+    ///
+    /// The delimiter is not included in the segments.
+    #[cfg(feature = "alloc")]
+    fn split(self, byte: u8) -> Split<Self>
+    where
+        Self: Sized,
+    {
+        Split {
+            inner: self,
+            delim: byte,
+        }
+    }
+
+    /// Turn this reader into an iterator over its lines, without the line terminator.
+    ///
+    /// Both `\n` and `\r\n` are accepted as the terminator.
+    #[cfg(feature = "alloc")]
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        Lines { inner: self }
+    }
 }
 
 pub trait Seek {
@@ -241,6 +547,36 @@ pub trait Seek {
     fn stream_position(&mut self) -> Result<u64> {
         self.seek(SeekFrom::Current(0))
     }
+
+    /// Seek back to the start of the stream.
+    fn rewind(&mut self) -> Result<()> {
+        self.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
+
+    /// Seek relative to the current position, discarding the new position.
+    ///
+    /// Implementations may use this to avoid work that is only required to report the position,
+    /// such as discarding a read buffer.
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        self.seek(SeekFrom::Current(offset))?;
+        Ok(())
+    }
+
+    /// Determine the length of the stream, in bytes.
+    ///
+    /// The default implementation seeks to the end and back, so the position is unchanged when
+    /// this returns successfully.
+    fn stream_len(&mut self) -> Result<u64> {
+        let old_pos = self.stream_position()?;
+        let len = self.seek(SeekFrom::End(0))?;
+
+        if old_pos != len {
+            self.seek(SeekFrom::Start(old_pos))?;
+        }
+
+        Ok(len)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -256,13 +592,22 @@ pub enum SeekFrom {
 /// generic `AllowStd<impl Write>` family if `std` is enabled, and on select instances such as
 /// `AllowStd<&mut [u8]>` otherwise. Additionally, the trait is implemented for all select types
 /// directly.
-///
-/// FIXME: should proxy `write_vectored` and `write_fmt`.
 pub trait Write {
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
     fn flush(&mut self) -> Result<()>;
 
+    /// Like `write`, but writing from a sequence of buffers.
+    ///
+    /// The default implementation writes the first non-empty buffer.
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let buf = bufs
+            .iter()
+            .find(|b| !b.is_empty())
+            .map_or(&[][..], |b| &**b);
+        self.write(buf)
+    }
+
     fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
         while !buf.is_empty() {
             match self.write(buf) {
@@ -274,16 +619,157 @@ pub trait Write {
         }
         Ok(())
     }
+
+    /// Write all buffers, retrying `write_vectored` until done.
+    ///
+    /// The buffers are modified in the process, their content afterwards is unspecified.
+    fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        // Skip over leading empty buffers.
+        IoSlice::advance_slices(&mut bufs, 0);
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs) {
+                Ok(0) => return Err(Error::from(ErrorKind::WriteZero)),
+                Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+                Err(ref e) if e.is_interrupted() => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Write formatted data, as used by the `write!` macro.
+    ///
+    /// Like `std`, this returns the first error of the underlying writer instead of a formatting
+    /// error.
+    fn write_fmt(&mut self, fmt: core::fmt::Arguments<'_>) -> Result<()> {
+        impls_always::write_fmt(self, fmt)
+    }
+
+    /// Borrow this writer, such that adaptors can be used without consuming it.
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// The standard writer that is wrapped, for dispatching to `std` in `copy`.
+    ///
+    /// The returned writer must write to the same destination as `self`. Sealed like
+    /// `__as_std_read`, this is not public API.
+    #[doc(hidden)]
+    fn __as_std_write(&mut self, _: sealed::Token) -> Option<__StdWrite<'_>> {
+        None
+    }
 }
 
+/// The result of `Read::__as_std_read`.
+///
+/// The hook exists in every configuration, such that our forwarding impls need not depend on the
+/// `std` feature. Without `std` there is no standard reader and this type is uninhabited.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub type __StdRead<'a> = &'a mut dyn std::io::Read;
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub type __StdRead<'a> = &'a mut __NoStd;
+
+/// The result of `Write::__as_std_write`, uninhabited without `std` like `__StdRead`.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub type __StdWrite<'a> = &'a mut dyn std::io::Write;
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub type __StdWrite<'a> = &'a mut __NoStd;
+
+mod sealed {
+    /// Passed to the hidden `std` dispatch hooks, which makes them private to this crate.
+    ///
+    /// The type is public to appear in the trait, but can not be named outside of this crate.
+    pub struct Token(pub(crate) ());
+}
+
+/// Uninhabited, the standard reader or writer without `std`.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub enum __NoStd {}
+
+/// Copy all data from a reader to a writer, returning the number of bytes copied.
+///
+/// This uses a small buffer on the stack. With `std`, when both sides are `AllowStd` wrappers of
+/// standard readers and writers, this is `std::io::copy` instead, which may use faster platform
+/// specific means.
 pub fn copy<R, W>(read: &mut R, write: &mut W) -> Result<u64>
 where
     R: Read + ?Sized,
     W: Write + ?Sized,
 {
+    #[cfg(feature = "std")]
+    {
+        let read_hook = read.__as_std_read(sealed::Token(()));
+        if let (Some(read), Some(write)) = (read_hook, write.__as_std_write(sealed::Token(()))) {
+            return std::io::copy(read, write).map_err(Error::from);
+        }
+    }
+
     impls_always::stack_copy(read, write)
 }
 
+/// Like `copy`, but using `buffer` for the intermediate data.
+///
+/// Fails with `ErrorKind::InvalidInput` if the buffer is empty.
+pub fn copy_with_buffer<R, W>(read: &mut R, write: &mut W, buffer: &mut [u8]) -> Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    impls_always::copy_with_buffer(read, write, buffer)
+}
+
+/// Copy all data from a buffered reader to a writer, writing directly from its buffer.
+pub fn copy_buf<R, W>(read: &mut R, write: &mut W) -> Result<u64>
+where
+    R: BufRead + ?Sized,
+    W: Write + ?Sized,
+{
+    impls_always::copy_buf(read, write)
+}
+
+/// Create a connected reader and writer, buffering up to `capacity` bytes in memory.
+///
+/// Neither end blocks, see `PipeReader` and `PipeWriter` for the behavior when the pipe is empty
+/// or full. For a pipe between threads use `blocking_pipe`.
+///
+/// # Panics
+///
+/// When `capacity` is zero.
+#[cfg(feature = "alloc")]
+pub fn pipe(capacity: usize) -> (PipeReader, PipeWriter) {
+    pipe::pipe(capacity)
+}
+
+/// Create two connected endpoints, each buffering up to `capacity` bytes towards the other.
+///
+/// # Panics
+///
+/// When `capacity` is zero.
+#[cfg(feature = "alloc")]
+pub fn duplex(capacity: usize) -> (DuplexStream, DuplexStream) {
+    pipe::duplex(capacity)
+}
+
+/// Like `pipe`, but the ends can be sent to different threads and wait for each other.
+///
+/// # Panics
+///
+/// When `capacity` is zero.
+#[cfg(feature = "std")]
+pub fn blocking_pipe(capacity: usize) -> (BlockingPipeReader, BlockingPipeWriter) {
+    pipe::blocking_pipe(capacity)
+}
+
 pub fn empty() -> Empty {
     Empty { _private: () }
 }
@@ -327,11 +813,17 @@ pub fn repeat(byte: u8) -> Repeat {
 /// * `impl<T> Read for AllowStd<T> where T: crate::Read`
 pub struct AllowStd<T>(pub T);
 
-/// A type that never implements any of the `std::io` traits.
+/// A type that never implements any of the `std::io` traits by itself.
 ///
 /// This is the reverse escape hatch to `AllowStd`. It allows this crate to provide a generic impl
 /// that Rust knows can never collide with another blanket impl bounded by `std::io::Read` or
-/// `std::io::Write`.
+/// `std::io::Write`. With the `std` feature it implements the `std::io` traits for any wrapped
+/// implementor of the traits of this crate, such that it can be passed to `std` interfaces:
+///
+/// * `impl<T> std::io::Read for NotIo<T> where T: crate::Read`
+/// * `impl<T> std::io::Write for NotIo<T> where T: crate::Write`
+///
+/// The traits of this crate are forwarded in all configurations.
 pub struct NotIo<T>(pub T);
 
 mod impls_always;
@@ -357,3 +849,7 @@ mod impls_nostd_alloc;
 
 #[cfg(feature = "std")]
 mod impls_std;
+
+/// Impls shared by all configurations that link `std`.
+#[cfg(any(feature = "std", feature = "compat"))]
+mod impls_compat;
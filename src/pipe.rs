@@ -0,0 +1,163 @@
+use crate::alloc::{boxed::Box, rc::Rc, vec};
+use crate::RingBuffer;
+use core::cell::RefCell;
+
+/// The reading end of an in-memory pipe, created by `pipe`.
+///
+/// Reading fails with `ErrorKind::WouldBlock` while the pipe is empty and the writer is still
+/// alive. Once the writer has been dropped, the remaining data is read followed by end-of-file.
+pub struct PipeReader {
+    pub(crate) shared: Rc<RefCell<PipeState>>,
+}
+
+/// The writing end of an in-memory pipe, created by `pipe`.
+///
+/// Writing fails with `ErrorKind::WouldBlock` while the pipe is full, and with
+/// `ErrorKind::BrokenPipe` once the reader has been dropped.
+pub struct PipeWriter {
+    pub(crate) shared: Rc<RefCell<PipeState>>,
+}
+
+/// One of two connected endpoints, created by `duplex`.
+///
+/// Data written to one endpoint can be read from the other, with the semantics of a `pipe` in
+/// each direction.
+pub struct DuplexStream {
+    pub(crate) reader: PipeReader,
+    pub(crate) writer: PipeWriter,
+}
+
+pub(crate) struct PipeState {
+    pub(crate) ring: RingBuffer<Box<[u8]>>,
+    pub(crate) reader_alive: bool,
+}
+
+impl PipeState {
+    pub(crate) fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "pipe capacity must not be zero");
+        PipeState {
+            ring: RingBuffer::new(vec![0; capacity].into_boxed_slice()),
+            reader_alive: true,
+        }
+    }
+}
+
+pub(crate) fn pipe(capacity: usize) -> (PipeReader, PipeWriter) {
+    let shared = Rc::new(RefCell::new(PipeState::new(capacity)));
+    let writer = PipeWriter {
+        shared: shared.clone(),
+    };
+    (PipeReader { shared }, writer)
+}
+
+pub(crate) fn duplex(capacity: usize) -> (DuplexStream, DuplexStream) {
+    let (reader_a, writer_b) = pipe(capacity);
+    let (reader_b, writer_a) = pipe(capacity);
+    (
+        DuplexStream {
+            reader: reader_a,
+            writer: writer_a,
+        },
+        DuplexStream {
+            reader: reader_b,
+            writer: writer_b,
+        },
+    )
+}
+
+impl DuplexStream {
+    /// Split into the independent directions of this endpoint.
+    pub fn into_parts(self) -> (PipeReader, PipeWriter) {
+        (self.reader, self.writer)
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        self.shared.borrow_mut().reader_alive = false;
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        self.shared.borrow_mut().ring.close();
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::blocking::{BlockingPipeReader, BlockingPipeWriter};
+
+#[cfg(feature = "std")]
+pub(crate) use self::blocking::blocking_pipe;
+
+#[cfg(feature = "std")]
+mod blocking {
+    use super::PipeState;
+    use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+    /// The reading end of a pipe between threads, created by `blocking_pipe`.
+    ///
+    /// Reading waits while the pipe is empty and the writer is still alive.
+    pub struct BlockingPipeReader {
+        pub(crate) shared: Arc<Shared>,
+    }
+
+    /// The writing end of a pipe between threads, created by `blocking_pipe`.
+    ///
+    /// Writing waits while the pipe is full, and fails with `ErrorKind::BrokenPipe` once the
+    /// reader has been dropped.
+    pub struct BlockingPipeWriter {
+        pub(crate) shared: Arc<Shared>,
+    }
+
+    pub(crate) struct Shared {
+        state: Mutex<PipeState>,
+        /// Signalled on every change of the state.
+        changed: Condvar,
+    }
+
+    impl Shared {
+        pub(crate) fn lock(&self) -> MutexGuard<'_, PipeState> {
+            // The state is consistent between all operations, even if one of them panicked.
+            self.state.lock().unwrap_or_else(|err| err.into_inner())
+        }
+
+        pub(crate) fn wait<'a>(
+            &self,
+            guard: MutexGuard<'a, PipeState>,
+        ) -> MutexGuard<'a, PipeState> {
+            self.changed
+                .wait(guard)
+                .unwrap_or_else(|err| err.into_inner())
+        }
+
+        pub(crate) fn notify(&self) {
+            self.changed.notify_all();
+        }
+    }
+
+    pub(crate) fn blocking_pipe(capacity: usize) -> (BlockingPipeReader, BlockingPipeWriter) {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(PipeState::new(capacity)),
+            changed: Condvar::new(),
+        });
+        let writer = BlockingPipeWriter {
+            shared: shared.clone(),
+        };
+        (BlockingPipeReader { shared }, writer)
+    }
+
+    impl Drop for BlockingPipeReader {
+        fn drop(&mut self) {
+            self.shared.lock().reader_alive = false;
+            self.shared.notify();
+        }
+    }
+
+    impl Drop for BlockingPipeWriter {
+        fn drop(&mut self) {
+            self.shared.lock().ring.close();
+            self.shared.notify();
+        }
+    }
+}
@@ -1,4 +1,76 @@
 pub struct Take<R> {
     pub(crate) inner: R,
+    /// The size of the window, for seeking within it.
+    pub(crate) len: u64,
     pub(crate) limit: u64,
 }
+
+impl<R> Take<R> {
+    /// The number of bytes that can still be read.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Set the number of bytes that can be read, starting a new window at the current position.
+    ///
+    /// This also resets the window for seeking, which then starts at the current position. The
+    /// data before it can no longer be reached through this adaptor.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.len = limit;
+        self.limit = limit;
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly does not count towards the limit.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub(crate) fn position(&self) -> u64 {
+        self.len - self.limit
+    }
+}
+
+pub struct Chain<T, U> {
+    pub(crate) first: T,
+    pub(crate) second: U,
+    pub(crate) done_first: bool,
+}
+
+pub struct Bytes<R> {
+    pub(crate) inner: R,
+}
+
+#[cfg(feature = "alloc")]
+pub struct Split<B> {
+    pub(crate) inner: B,
+    pub(crate) delim: u8,
+}
+
+#[cfg(feature = "alloc")]
+pub struct Lines<B> {
+    pub(crate) inner: B,
+}
+
+impl<T, U> Chain<T, U> {
+    pub fn into_inner(self) -> (T, U) {
+        (self.first, self.second)
+    }
+
+    pub fn get_ref(&self) -> (&T, &U) {
+        (&self.first, &self.second)
+    }
+
+    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
+        (&mut self.first, &mut self.second)
+    }
+}
@@ -0,0 +1,72 @@
+/// A bounded first-in first-out byte queue, on a fixed storage.
+///
+/// The storage can be any byte buffer, such as an owned `[u8; N]` or a borrowed `&mut [u8]`. Data
+/// written to the buffer is read back in the same order. Since neither side can wait for the
+/// other, `Write` fails with `ErrorKind::WouldBlock` when the buffer is full, and `Read` fails with
+/// the same kind when it is empty. Once the buffer has been closed, reading the remaining data
+/// instead ends with a regular end-of-file and further writes fail with `ErrorKind::BrokenPipe`.
+pub struct RingBuffer<B> {
+    pub(crate) buf: B,
+    /// The index of the first byte to read.
+    pub(crate) head: usize,
+    pub(crate) len: usize,
+    pub(crate) closed: bool,
+}
+
+impl<B> RingBuffer<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    pub fn new(buf: B) -> Self {
+        RingBuffer {
+            buf,
+            head: 0,
+            len: 0,
+            closed: false,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// The number of bytes that can be read.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Mark the end of the data, such that readers reach end-of-file after the remaining data.
+    pub fn close(&mut self) {
+        self.closed = true;
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Discard all data and reopen the buffer.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.closed = false;
+    }
+
+    /// The buffered data, in two parts as it may wrap around the end of the storage.
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let buf = self.buf.as_ref();
+        let first = self.len.min(buf.len() - self.head);
+        (&buf[self.head..][..first], &buf[..self.len - first])
+    }
+
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
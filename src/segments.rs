@@ -0,0 +1,97 @@
+/// A reader over a series of slices, such as a scatter list or the two halves of a ring buffer.
+///
+/// The data is read as if the slices were concatenated, without copying them into a contiguous
+/// buffer first. As a `BufRead` it provides the data one segment at a time. Seeking is supported
+/// across segment boundaries, to positions beyond the end as for `Cursor`.
+///
+/// The list of slices may be shorter lived than the data it refers to, which is borrowed for `'b`.
+#[derive(Clone, Debug)]
+pub struct Segments<'a, 'b> {
+    pub(crate) segments: &'a [&'b [u8]],
+    /// The total length of all segments.
+    pub(crate) len: u64,
+    pub(crate) pos: u64,
+    /// The segment containing the byte at `pos`, or the number of segments at the end.
+    pub(crate) index: usize,
+    pub(crate) offset: usize,
+}
+
+/// A reader over slices produced by an iterator.
+///
+/// This is the streaming variant of `Segments`, which can not seek.
+#[derive(Clone, Debug)]
+pub struct SegmentIter<'a, I> {
+    pub(crate) iter: I,
+    pub(crate) current: &'a [u8],
+}
+
+impl<'a, 'b> Segments<'a, 'b> {
+    pub fn new(segments: &'a [&'b [u8]]) -> Self {
+        let len = segments.iter().map(|s| s.len() as u64).sum();
+        Segments {
+            segments,
+            len,
+            pos: 0,
+            index: 0,
+            offset: 0,
+        }
+    }
+
+    /// The total length of all segments.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn get_ref(&self) -> &'a [&'b [u8]] {
+        self.segments
+    }
+
+    pub fn into_inner(self) -> &'a [&'b [u8]] {
+        self.segments
+    }
+
+    pub(crate) fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+        self.index = self.segments.len();
+        self.offset = 0;
+
+        let mut remaining = pos;
+        for (index, segment) in self.segments.iter().enumerate() {
+            match remaining.checked_sub(segment.len() as u64) {
+                Some(rest) => remaining = rest,
+                None => {
+                    self.index = index;
+                    self.offset = remaining as usize;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> SegmentIter<'a, I> {
+    pub fn new<T>(segments: T) -> Self
+    where
+        T: IntoIterator<IntoIter = I>,
+    {
+        SegmentIter {
+            iter: segments.into_iter(),
+            current: &[],
+        }
+    }
+
+    /// Get the iterator of the remaining segments.
+    ///
+    /// The unread rest of the current segment is lost.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
@@ -0,0 +1,38 @@
+/// Limits the number of bytes written to a writer.
+///
+/// Once the limit has been reached, writing any more data fails with `ErrorKind::WriteZero`. This
+/// is the counterpart of `Take` for bounded output.
+pub struct WriteLimit<W> {
+    pub(crate) inner: W,
+    pub(crate) limit: u64,
+}
+
+impl<W> WriteLimit<W> {
+    pub fn new(inner: W, limit: u64) -> Self {
+        WriteLimit { inner, limit }
+    }
+
+    /// The number of bytes that can still be written.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly does not count towards the limit.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
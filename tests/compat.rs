@@ -0,0 +1,41 @@
+use not_io::{Error, ErrorKind};
+
+// Make sure that this includes the no-`alloc` subset of tests.
+#[path = "no_alloc.rs"]
+mod _compat;
+
+#[test]
+fn error_kind_match() {
+    fn is_eof(kind: ErrorKind) -> bool {
+        match kind {
+            ErrorKind::UnexpectedEof => true,
+            // Required in all configurations, with or without `compat`.
+            _ => false,
+        }
+    }
+
+    assert!(is_eof(Error::from(ErrorKind::UnexpectedEof).kind()));
+    assert!(!is_eof(ErrorKind::Other));
+}
+
+#[test]
+fn error_into_std() {
+    let err = std::io::Error::from(Error::from(ErrorKind::WriteZero));
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+
+    let err = std::io::Error::from(Error::new_static(ErrorKind::InvalidData, "bad magic"));
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "bad magic");
+
+    let err = Error::from(err);
+    assert_eq!(err.static_message(), Some("bad magic"));
+
+    let err = Error::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn error_trait() {
+    let err: Box<dyn std::error::Error> = Box::new(Error::from(ErrorKind::InvalidInput));
+    assert_eq!(err.to_string(), "invalid input parameter");
+}
@@ -1,8 +1,11 @@
-use not_io::{AllowStd, BufRead, Cursor, Read, Seek, SeekFrom, Write};
+use not_io::{
+    AllowStd, BufRead, BufReader, BufWriter, Cursor, LineWriter, NotIo, Read, Seek, SeekFrom, Write,
+};
 
 fn is_read<R: Read>() {}
 fn is_write<W: Write>() {}
 fn is_buf_read<R: BufRead>() {}
+fn is_seek<R: Seek>() {}
 
 const XXX: () = {
     let _ = is_read::<&'static [u8]>;
@@ -14,6 +17,22 @@ const XXX: () = {
     let _ = is_read::<Cursor<&'static str>>; // From AsRef<[u8]>.
     let _ = is_write::<Cursor<&'static mut [u8]>>;
     let _ = is_buf_read::<Cursor<&'static [u8]>>;
+    let _ = is_buf_read::<BufReader<&'static [u8], [u8; 16]>>;
+    let _ = is_buf_read::<BufReader<&'static [u8], &'static mut [u8]>>;
+    let _ = is_write::<BufWriter<&'static mut [u8], [u8; 16]>>;
+    let _ = is_write::<LineWriter<&'static mut [u8], &'static mut [u8]>>;
+    let _ = is_buf_read::<AllowStd<&'static [u8]>>;
+    let _ = is_buf_read::<AllowStd<Cursor<&'static [u8]>>>;
+    let _ = is_seek::<AllowStd<Cursor<&'static [u8]>>>;
+    let _ = is_write::<AllowStd<Cursor<&'static mut [u8]>>>;
+    let _ = is_buf_read::<NotIo<&'static [u8]>>;
+    let _ = is_seek::<NotIo<Cursor<&'static [u8]>>>;
+    let _ = is_write::<NotIo<&'static mut [u8]>>;
+    let _ = is_buf_read::<not_io::Chain<&'static [u8], Cursor<&'static [u8]>>>;
+    let _ = is_read::<&'static mut Cursor<&'static [u8]>>;
+    let _ = is_write::<&'static mut Cursor<&'static mut [u8]>>;
+    let _ = is_seek::<not_io::Take<Cursor<&'static [u8]>>>;
+    let _ = is_write::<not_io::WriteLimit<&'static mut [u8]>>;
 };
 
 #[test]
@@ -44,3 +63,770 @@ fn copy() {
         matches!(not_io::copy(&mut &SOURCE[..], &mut not_io::sink()), Ok(len) if len as usize == SOURCE.len())
     );
 }
+
+#[test]
+fn buf_reader() {
+    const SOURCE: &[u8] = b"Hello, world!";
+    let mut reader = BufReader::new(SOURCE, [0u8; 4]);
+    assert_eq!(reader.capacity(), 4);
+    assert!(reader.buffer().is_empty());
+
+    assert!(matches!(reader.fill_buf(), Ok(b"Hell")));
+    reader.consume(2);
+    assert_eq!(reader.buffer(), b"ll");
+
+    let mut buffer = [0u8; 8];
+    assert!(matches!(reader.read(&mut buffer), Ok(2)));
+    assert_eq!(&buffer[..2], b"ll");
+
+    // An empty buffer and a large read goes directly to the reader.
+    assert!(matches!(reader.read(&mut buffer), Ok(8)));
+    assert_eq!(&buffer, b"o, world");
+    assert_eq!(reader.into_inner(), b"!");
+}
+
+#[test]
+fn buf_reader_seek() {
+    const SOURCE: &str = "Hello, world!";
+    let mut storage = [0u8; 4];
+    let mut reader = BufReader::new(Cursor::new(SOURCE), &mut storage[..]);
+
+    assert!(matches!(reader.fill_buf(), Ok(b"Hell")));
+    reader.consume(1);
+    assert!(matches!(reader.stream_position(), Ok(1)));
+
+    assert!(matches!(reader.seek(SeekFrom::Current(6)), Ok(7)));
+    assert!(reader.buffer().is_empty());
+    assert!(matches!(reader.fill_buf(), Ok(b"worl")));
+
+    assert!(reader.seek(SeekFrom::Current(i64::MIN)).is_err());
+    assert!(matches!(reader.seek(SeekFrom::End(-1)), Ok(12)));
+    assert!(matches!(reader.fill_buf(), Ok(b"!")));
+}
+
+#[test]
+fn buf_writer() {
+    let mut target = [0u8; 16];
+    let mut writer = BufWriter::new(Cursor::new(&mut target[..]), [0u8; 4]);
+
+    writer.write_all(b"Hel").unwrap();
+    assert_eq!(writer.buffer(), b"Hel");
+    assert_eq!(writer.get_ref().position(), 0);
+
+    writer.write_all(b"lo").unwrap();
+    assert_eq!(writer.buffer(), b"lo");
+    assert_eq!(writer.get_ref().position(), 3);
+
+    // Too large for the buffer, written directly after flushing.
+    writer.write_all(b", world!").unwrap();
+    assert!(writer.buffer().is_empty());
+
+    let cursor = writer.into_inner().ok().unwrap();
+    assert_eq!(cursor.position(), 13);
+    assert_eq!(&target[..13], b"Hello, world!");
+}
+
+#[test]
+fn write_slice_short() {
+    let mut target = [0u8; 2];
+    let mut slice = &mut target[..];
+    assert!(matches!(slice.write(b"Hello"), Ok(2)));
+    assert!(slice.is_empty());
+    assert!(matches!(slice.write(b"!"), Ok(0)));
+    assert_eq!(target, *b"He");
+}
+
+#[test]
+fn buf_writer_into_inner_error() {
+    let mut target = [0u8; 2];
+    let mut writer = BufWriter::new(&mut target[..], [0u8; 8]);
+    writer.write_all(b"Hello").unwrap();
+
+    let err = match writer.into_inner() {
+        Ok(_) => panic!("target should be too small"),
+        Err(err) => err,
+    };
+
+    let writer = err.into_inner();
+    assert_eq!(writer.buffer(), b"llo");
+    let (rest, storage, len) = writer.into_parts();
+    assert!(rest.is_empty());
+    assert_eq!(&storage[..len], b"llo");
+    assert_eq!(target, *b"He");
+}
+
+#[test]
+fn line_writer() {
+    let mut target = [0u8; 16];
+    let mut writer = LineWriter::new(Cursor::new(&mut target[..]), [0u8; 8]);
+
+    writer.write_all(b"Hello").unwrap();
+    assert_eq!(writer.get_ref().position(), 0);
+
+    writer.write_all(b",\nwor").unwrap();
+    assert_eq!(writer.get_ref().position(), 7);
+
+    assert!(matches!(writer.write(b"ld\n!"), Ok(4)));
+    assert_eq!(writer.get_ref().position(), 13);
+
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().position(), 14);
+    drop(writer);
+    assert_eq!(&target[..14], b"Hello,\nworld\n!");
+}
+
+#[test]
+fn write_fmt() {
+    let mut target = [0u8; 16];
+    let mut cursor = Cursor::new(&mut target[..]);
+    write!(cursor, "Hello, {}!", 42).unwrap();
+    assert_eq!(cursor.position(), 10);
+    assert_eq!(&target[..10], b"Hello, 42!");
+
+    let mut short = [0u8; 4];
+    assert!(write!(&mut short[..], "Hello").is_err());
+}
+
+#[test]
+fn fmt_writer() {
+    use core::fmt::Write as _;
+    let mut target = [0u8; 4];
+    let mut writer = not_io::FmtWriter::new(&mut target[..]);
+
+    assert!(write!(writer, "{}", 42).is_ok());
+    assert!(writer.take_error().is_none());
+    assert!(write!(writer, "Hello").is_err());
+    assert!(writer.take_error().is_some());
+    assert_eq!(target, *b"42He");
+}
+
+#[test]
+fn utf8_writer() {
+    struct Utf8Bytes(&'static [u8]);
+
+    impl core::fmt::Display for Utf8Bytes {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut writer = not_io::Utf8Writer::new(f);
+            // Split all characters across writes.
+            for chunk in self.0.chunks(1) {
+                writer.write_all(chunk).map_err(|_| core::fmt::Error)?;
+            }
+            Ok(())
+        }
+    }
+
+    const SOURCE: &str = "Hällo, wörld ✓";
+    let mut target = [0u8; 32];
+    let mut cursor = Cursor::new(&mut target[..]);
+    write!(cursor, "{}", Utf8Bytes(SOURCE.as_bytes())).unwrap();
+    assert_eq!(cursor.position(), SOURCE.len() as u64);
+    assert_eq!(&target[..SOURCE.len()], SOURCE.as_bytes());
+
+    let mut cursor = Cursor::new(&mut target[..]);
+    assert!(write!(cursor, "{}", Utf8Bytes(b"ok\xfe")).is_err());
+    assert_eq!(cursor.position(), 2);
+}
+
+#[test]
+fn read_vectored() {
+    use not_io::IoSliceMut;
+    const SOURCE: &[u8] = b"Hello, world!";
+    let (mut first, mut second) = ([0u8; 5], [0u8; 16]);
+    let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+
+    let mut cursor = Cursor::new(SOURCE);
+    assert!(matches!(cursor.read_vectored(&mut bufs), Ok(13)));
+    assert_eq!(cursor.position(), 13);
+    assert_eq!(&first, b"Hello");
+    assert_eq!(&second[..8], b", world!");
+}
+
+#[test]
+fn write_all_vectored() {
+    use not_io::IoSlice;
+    let mut target = [0u8; 16];
+    let mut bufs = [
+        IoSlice::new(b""),
+        IoSlice::new(b"Hello"),
+        IoSlice::new(b", "),
+        IoSlice::new(b"world!"),
+    ];
+
+    let mut cursor = Cursor::new(&mut target[..]);
+    cursor.write_all_vectored(&mut bufs).unwrap();
+    assert_eq!(cursor.position(), 13);
+    assert_eq!(&target[..13], b"Hello, world!");
+
+    let mut short = [0u8; 4];
+    let mut bufs = [IoSlice::new(b"Hel"), IoSlice::new(b"lo")];
+    assert!(matches!((&mut short[..]).write_vectored(&bufs), Ok(4)));
+    assert!((&mut short[..]).write_all_vectored(&mut bufs).is_err());
+}
+
+#[test]
+fn io_slice_advance() {
+    use not_io::IoSlice;
+    let mut bufs = [
+        IoSlice::new(b"Hel"),
+        IoSlice::new(b"lo, "),
+        IoSlice::new(b"world!"),
+    ];
+    let mut bufs = &mut bufs[..];
+
+    IoSlice::advance_slices(&mut bufs, 5);
+    assert_eq!(bufs.len(), 2);
+    assert_eq!(&*bufs[0], b", ");
+
+    IoSlice::advance_slices(&mut bufs, 8);
+    assert!(bufs.is_empty());
+}
+
+#[test]
+fn error_kind() {
+    use not_io::{Error, ErrorKind};
+    let mut short = [0u8; 4];
+    let err = (&mut short[..]).write_all(b"Hello").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+
+    let err = (&b"Hel"[..]).read_exact(&mut short).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let err = Error::from(ErrorKind::InvalidData);
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn error_static() {
+    use not_io::{Error, ErrorKind};
+    const BAD_MAGIC: Error =
+        Error::new_static(ErrorKind::InvalidData, "bad magic number in header");
+    const BAD_VERSION: Error = Error::new_static_code(ErrorKind::Unsupported, "bad version", 3);
+
+    let err = BAD_MAGIC;
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.static_message(), Some("bad magic number in header"));
+    assert_eq!(err.code(), None);
+
+    let err = BAD_VERSION;
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+    assert_eq!(err.code(), Some(3));
+
+    assert_eq!(Error::from(ErrorKind::InvalidData).static_message(), None);
+}
+
+#[test]
+fn error_display() {
+    use not_io::{Error, ErrorKind};
+    let mut target = [0u8; 32];
+    let mut cursor = Cursor::new(&mut target[..]);
+    write!(cursor, "{}", Error::from(ErrorKind::UnexpectedEof)).unwrap();
+    let len = cursor.position() as usize;
+    assert_eq!(&target[..len], b"unexpected end of file");
+
+    let mut cursor = Cursor::new(&mut target[..]);
+    let err = Error::new_static_code(ErrorKind::Unsupported, "bad version", 3);
+    write!(cursor, "{}", err).unwrap();
+    let len = cursor.position() as usize;
+    assert_eq!(&target[..len], b"bad version (code 3)");
+}
+
+#[test]
+#[cfg(not(feature = "std"))]
+fn error_clone_eq() {
+    use not_io::{Error, ErrorKind};
+    let err = Error::new_static_code(ErrorKind::Unsupported, "bad version", 3);
+    assert_eq!(err.clone(), err);
+    assert_ne!(
+        err,
+        Error::new_static(ErrorKind::Unsupported, "bad version")
+    );
+    assert_ne!(err, Error::from(ErrorKind::Unsupported));
+
+    let err = Error::from(ErrorKind::UnexpectedEof);
+    assert_eq!(err.clone(), Error::from(ErrorKind::UnexpectedEof));
+    assert_ne!(err, Error::from(ErrorKind::WriteZero));
+}
+
+#[test]
+fn allow_std_cursor() {
+    const SOURCE: &str = "Hello, world!";
+    let mut stream = AllowStd(Cursor::new(SOURCE));
+
+    assert!(matches!(stream.seek(SeekFrom::End(-6)), Ok(7)));
+    assert!(matches!(stream.fill_buf(), Ok(b"world!")));
+}
+
+#[test]
+fn chain() {
+    const HEADER: &[u8] = b"Hello";
+    let mut reader = HEADER.chain(Cursor::new(", world!"));
+
+    assert!(matches!(reader.fill_buf(), Ok(b"Hello")));
+    reader.consume(5);
+    assert!(matches!(reader.fill_buf(), Ok(b", world!")));
+
+    let mut buffer = [0u8; 4];
+    reader.read_exact(&mut buffer).unwrap();
+    assert_eq!(&buffer, b", wo");
+
+    let (header, rest) = reader.into_inner();
+    assert!(header.is_empty());
+    assert_eq!(rest.position(), 4);
+}
+
+#[test]
+fn bytes() {
+    let reader = Cursor::new("Hi!");
+    let mut bytes = reader.bytes();
+    assert!(matches!(bytes.next(), Some(Ok(b'H'))));
+    assert!(matches!(bytes.next(), Some(Ok(b'i'))));
+    assert!(matches!(bytes.next(), Some(Ok(b'!'))));
+    assert!(bytes.next().is_none());
+}
+
+#[test]
+fn by_ref() {
+    let mut reader = Cursor::new("Hello, world!");
+    let mut buffer = [0u8; 5];
+
+    reader.by_ref().read_exact(&mut buffer).unwrap();
+    assert_eq!(reader.position(), 5);
+
+    let mut target = [0u8; 8];
+    let mut writer = Cursor::new(&mut target[..]);
+    Write::by_ref(&mut writer).write_all(&buffer).unwrap();
+    assert_eq!(writer.position(), 5);
+    assert_eq!(&target[..5], b"Hello");
+}
+
+#[test]
+fn take_accessors() {
+    let mut reader = Cursor::new("\x05Hello, world!").take(1);
+    let mut len = [0u8; 1];
+    reader.read_exact(&mut len).unwrap();
+    assert_eq!(reader.limit(), 0);
+
+    reader.set_limit(u64::from(len[0]));
+    let mut section = [0u8; 8];
+    assert!(matches!(reader.read(&mut section), Ok(5)));
+    assert_eq!(&section[..5], b"Hello");
+
+    assert_eq!(reader.get_ref().position(), 6);
+    let inner = reader.into_inner();
+    assert_eq!(inner.position(), 6);
+}
+
+#[test]
+fn take_seek() {
+    let mut inner = Cursor::new("Hello, world!");
+    inner.set_position(7);
+    let mut reader = inner.take(3);
+
+    assert!(matches!(reader.seek(SeekFrom::End(-1)), Ok(2)));
+    assert!(matches!(reader.fill_buf(), Ok(b"r")));
+    assert!(matches!(reader.seek(SeekFrom::Start(0)), Ok(0)));
+    assert!(matches!(reader.fill_buf(), Ok(b"wor")));
+    assert!(matches!(reader.stream_position(), Ok(0)));
+
+    assert!(reader.seek(SeekFrom::Start(4)).is_err());
+    assert!(reader.seek(SeekFrom::Current(-1)).is_err());
+    assert_eq!(reader.get_ref().position(), 7);
+}
+
+#[test]
+fn take_set_limit_window() {
+    let mut reader = Cursor::new("Hello, world!").take(10);
+    let mut buf = [0u8; 7];
+    reader.read_exact(&mut buf).unwrap();
+
+    // The new window starts at the current position.
+    reader.set_limit(3);
+    assert!(matches!(reader.stream_position(), Ok(0)));
+    assert!(matches!(reader.seek(SeekFrom::End(0)), Ok(3)));
+    assert!(matches!(reader.seek(SeekFrom::Start(0)), Ok(0)));
+    assert!(reader.seek(SeekFrom::Current(-1)).is_err());
+    assert!(matches!(reader.fill_buf(), Ok(b"wor")));
+}
+
+#[test]
+fn take_seek_error() {
+    /// Fails every seek, but not reading.
+    struct NoSeek<'a>(Cursor<&'a str>);
+
+    impl Read for NoSeek<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> not_io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Seek for NoSeek<'_> {
+        fn seek(&mut self, _: SeekFrom) -> not_io::Result<u64> {
+            Err(not_io::ErrorKind::Unsupported.into())
+        }
+    }
+
+    let mut reader = NoSeek(Cursor::new("Hello, world!")).take(8);
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf).unwrap();
+    assert!(reader.seek(SeekFrom::Start(5)).is_err());
+    assert_eq!(reader.limit(), 6);
+    assert!(matches!(reader.stream_position(), Ok(2)));
+    assert_eq!(reader.get_ref().0.position(), 2);
+}
+
+#[test]
+fn take_seek_unbounded() {
+    let mut inner = Cursor::new("Hello, world!");
+    inner.set_position(7);
+    let mut reader = inner.take(u64::MAX);
+
+    assert!(matches!(reader.seek(SeekFrom::End(-1)), Ok(5)));
+    assert!(matches!(reader.fill_buf(), Ok(b"!")));
+    assert!(matches!(reader.seek(SeekFrom::End(0)), Ok(6)));
+    assert_eq!(reader.get_ref().position(), 13);
+    assert_eq!(reader.limit(), u64::MAX - 6);
+}
+
+#[test]
+fn write_limit() {
+    use not_io::{ErrorKind, WriteLimit};
+    let mut target = [0u8; 16];
+    let mut writer = WriteLimit::new(Cursor::new(&mut target[..]), 8);
+
+    assert!(matches!(writer.write(b"Hello, "), Ok(7)));
+    assert!(matches!(writer.write(b"world!"), Ok(1)));
+    assert_eq!(writer.limit(), 0);
+
+    let err = writer.write(b"orld!").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert!(matches!(writer.write(b""), Ok(0)));
+
+    writer.set_limit(2);
+    assert_eq!(
+        writer.write_all(b"orld!").unwrap_err().kind(),
+        ErrorKind::WriteZero
+    );
+    assert_eq!(writer.into_inner().position(), 10);
+    assert_eq!(&target[..10], b"Hello, wor");
+}
+
+#[test]
+fn seek_rewind_relative_len() {
+    let mut cursor = Cursor::new("Hello, world!");
+    let mut magic = [0u8; 5];
+    cursor.read_exact(&mut magic).unwrap();
+    assert_eq!(&magic, b"Hello");
+
+    assert!(matches!(cursor.stream_len(), Ok(13)));
+    cursor.rewind().unwrap();
+    assert_eq!(cursor.position(), 0);
+    cursor.seek_relative(7).unwrap();
+    assert!(matches!(cursor.fill_buf(), Ok(b"world!")));
+    assert!(cursor.seek_relative(-8).is_err());
+
+    let mut empty = not_io::empty();
+    empty.seek_relative(-4).unwrap();
+    empty.rewind().unwrap();
+    assert!(matches!(empty.stream_len(), Ok(0)));
+}
+
+#[test]
+fn buf_reader_seek_relative() {
+    let mut reader = not_io::BufReader::new(Cursor::new("Hello, world!"), [0u8; 8]);
+    assert!(matches!(reader.fill_buf(), Ok(b"Hello, w")));
+
+    // Within the buffer, the inner reader is not touched.
+    reader.seek_relative(7).unwrap();
+    assert!(matches!(reader.fill_buf(), Ok(b"w")));
+    reader.seek_relative(-2).unwrap();
+    assert!(matches!(reader.fill_buf(), Ok(b", w")));
+    assert_eq!(reader.get_ref().position(), 8);
+
+    reader.seek_relative(4).unwrap();
+    assert!(matches!(reader.fill_buf(), Ok(b"rld!")));
+    assert!(matches!(reader.stream_len(), Ok(13)));
+    assert!(matches!(reader.stream_position(), Ok(9)));
+}
+
+#[test]
+fn skip_until() {
+    let mut storage = [0u8; 4];
+    let mut reader = BufReader::new(Cursor::new("# comment\nkey"), &mut storage[..]);
+
+    assert!(matches!(reader.has_data_left(), Ok(true)));
+    assert!(matches!(reader.skip_until(b'\n'), Ok(10)));
+    assert!(matches!(reader.fill_buf(), Ok(b"ke")));
+    assert!(matches!(reader.skip_until(b'\n'), Ok(3)));
+    assert!(matches!(reader.has_data_left(), Ok(false)));
+    assert!(matches!(reader.skip_until(b'\n'), Ok(0)));
+}
+
+#[test]
+fn delimited_read() {
+    use not_io::{delimited, ErrorKind};
+    let mut storage = [0u8; 4];
+    let mut reader = BufReader::new(Cursor::new("help\nls -la\nexit"), &mut storage[..]);
+    let mut line = [0u8; 6];
+
+    assert_eq!(
+        delimited::read_line(&mut reader, &mut line).unwrap(),
+        "help\n"
+    );
+
+    let err = delimited::read_line(&mut reader, &mut line).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BufferFull);
+    assert_eq!(&line, b"ls -la");
+    assert!(matches!(reader.skip_until(b'\n'), Ok(1)));
+
+    assert_eq!(
+        delimited::read_until(&mut reader, b'\n', &mut line).unwrap(),
+        b"exit"
+    );
+    assert!(delimited::read_until(&mut reader, b'\n', &mut line)
+        .unwrap()
+        .is_empty());
+
+    // A segment that fills the buffer exactly is not an error at the end of the stream.
+    let mut exact = &b"quit"[..];
+    assert_eq!(
+        delimited::read_until(&mut exact, b'\n', &mut line[..4]).unwrap(),
+        b"quit"
+    );
+
+    let mut invalid = Cursor::new(&b"\xff\n"[..]);
+    let err = delimited::read_line(&mut invalid, &mut line).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_buf() {
+    use core::mem::MaybeUninit;
+    use not_io::BorrowedBuf;
+
+    let mut storage = [MaybeUninit::<u8>::uninit(); 8];
+    let mut buf = BorrowedBuf::from(&mut storage[..]);
+
+    let mut source = &b"Hello"[..];
+    source.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"Hello");
+    assert_eq!(buf.init_len(), 5);
+    assert!(source.is_empty());
+
+    let mut cursor = Cursor::new(", world!");
+    cursor.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"Hello, w");
+    assert_eq!(cursor.position(), 3);
+
+    // The default implementation initializes the remainder once.
+    let mut reader = not_io::repeat(b'!').take(2);
+    buf.clear();
+    reader.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"!!");
+    assert_eq!(buf.init_len(), 8);
+
+    let mut unfilled = buf.unfilled();
+    assert_eq!(unfilled.capacity(), 6);
+    unfilled.append(b"?");
+    assert_eq!(unfilled.written(), 1);
+    assert_eq!(unfilled.init_mut().len(), 5);
+    assert_eq!(buf.filled(), b"!!?");
+}
+
+#[test]
+fn size_hint() {
+    let mut cursor = Cursor::new("Hello, world!");
+    cursor.set_position(7);
+    assert_eq!(cursor.size_hint(), (6, Some(6)));
+
+    let chain = (&b"Hello"[..]).chain(cursor);
+    assert_eq!(chain.size_hint(), (11, Some(11)));
+    assert_eq!(chain.take(4).size_hint(), (4, Some(4)));
+
+    let repeat = not_io::repeat(0);
+    assert_eq!(repeat.size_hint(), (usize::MAX, None));
+    assert_eq!(repeat.take(3).size_hint(), (3, Some(3)));
+    assert_eq!(not_io::empty().size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn copy_variants() {
+    use not_io::ErrorKind;
+    let mut target = [0u8; 16];
+
+    let mut source = Cursor::new("Hello, world!");
+    let mut writer = Cursor::new(&mut target[..]);
+    assert!(matches!(not_io::copy_buf(&mut source, &mut writer), Ok(13)));
+    assert_eq!(writer.position(), 13);
+
+    let mut buffer = [0u8; 3];
+    let mut source = Cursor::new("Hello");
+    let mut writer = Cursor::new(&mut target[..]);
+    assert!(matches!(
+        not_io::copy_with_buffer(&mut source, &mut writer, &mut buffer),
+        Ok(5)
+    ));
+    assert_eq!(&target[..13], b"Hello, world!");
+
+    let err =
+        not_io::copy_with_buffer(&mut not_io::empty(), &mut not_io::sink(), &mut []).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn forward_mut_ref() {
+    fn decode<R: BufRead + Seek>(mut reader: R) -> u64 {
+        reader.skip_until(b' ').unwrap();
+        reader.stream_position().unwrap()
+    }
+
+    let mut cursor = Cursor::new("magic data");
+    assert_eq!(decode(&mut cursor), 6);
+    assert_eq!(decode(&mut cursor), 10);
+
+    let mut reader = Cursor::new("Hello, world!");
+    let mut buffer = [0u8; 8];
+    assert!(matches!(reader.by_ref().take(5).read(&mut buffer), Ok(5)));
+    assert_eq!(reader.position(), 5);
+
+    let mut dyn_reader: &mut dyn Read = &mut Cursor::new("Hello");
+    let mut buf = [0u8; 5];
+    (&mut dyn_reader).read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Hello");
+
+    let mut target = [0u8; 4];
+    let mut writer = Cursor::new(&mut target[..]);
+    let mut dyn_writer: &mut dyn Write = &mut writer;
+    assert!(Write::write_all(&mut dyn_writer, b"Hello").is_err());
+    assert_eq!(&target, b"Hell");
+}
+
+#[test]
+fn cursor_array_frame() {
+    let mut frame = Cursor::new([0u8; 8]);
+    frame.write_all(&[0x7e, 3]).unwrap();
+    frame.write_all(b"abc").unwrap();
+
+    let (written, rest) = frame.split_at_position();
+    assert_eq!(written, [0x7e, 3, b'a', b'b', b'c']);
+    assert_eq!(rest.len(), 3);
+    assert_eq!(frame.remaining_slice(), [0, 0, 0]);
+
+    let (_, rest) = frame.split_at_position_mut();
+    rest.copy_from_slice(b"xyz");
+    assert!(frame.write(b"!").is_ok());
+    assert_eq!(frame.remaining_slice(), b"yz");
+    assert!(matches!(frame.write(b"overflow"), Ok(2)));
+    assert!(frame.is_empty());
+    assert_eq!(&frame.get_ref()[5..], b"!ov");
+
+    frame.set_position(100);
+    assert_eq!(frame.split_at_position().0.len(), 8);
+    assert!(frame.remaining_slice().is_empty());
+}
+
+#[test]
+fn fixed_buf() {
+    use not_io::{ErrorKind, FixedBuf};
+    let mut buf = FixedBuf::<8>::new();
+    write!(buf, "{}-{}", 12, 34).unwrap();
+    assert_eq!(buf.as_slice(), b"12-34");
+    assert_eq!(buf.remaining_capacity(), 3);
+
+    let mut head = [0u8; 2];
+    buf.read_exact(&mut head).unwrap();
+    assert_eq!(&head, b"12");
+    assert!(matches!(buf.fill_buf(), Ok(b"-34")));
+
+    let err = buf.write_all(b"5678").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(buf.as_slice(), b"12-34567");
+    assert_eq!(buf.write(b"9").unwrap_err().kind(), ErrorKind::WriteZero);
+
+    assert!(matches!(buf.seek(SeekFrom::End(-3)), Ok(5)));
+    assert!(matches!(buf.fill_buf(), Ok(b"567")));
+    assert!(buf.seek(SeekFrom::Current(4)).is_err());
+    buf.rewind().unwrap();
+    assert_eq!(buf.size_hint(), (8, Some(8)));
+
+    buf.clear();
+    assert!(buf.is_empty());
+    assert!(matches!(buf.write(b"ok"), Ok(2)));
+    assert_eq!(buf.len(), 2);
+}
+
+#[test]
+fn segments() {
+    use not_io::{SegmentIter, Segments};
+    let parts: &[&[u8]] = &[b"Hel", b"", b"lo, ", b"world!"];
+
+    let mut reader = Segments::new(parts);
+    assert_eq!(reader.len(), 13);
+    let mut buf = [0u8; 5];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Hello");
+    assert!(matches!(reader.fill_buf(), Ok(b", ")));
+
+    assert!(matches!(reader.seek(SeekFrom::End(-6)), Ok(7)));
+    assert!(matches!(reader.fill_buf(), Ok(b"world!")));
+    assert!(matches!(reader.seek(SeekFrom::Current(-5)), Ok(2)));
+    assert!(matches!(reader.fill_buf(), Ok(b"l")));
+    assert!(reader.seek(SeekFrom::Current(-3)).is_err());
+    assert_eq!(reader.size_hint(), (11, Some(11)));
+
+    assert!(matches!(reader.seek(SeekFrom::Start(20)), Ok(20)));
+    assert!(matches!(reader.read(&mut buf), Ok(0)));
+
+    // The segments outlive the list referring to them.
+    let data = b"Hello, world!";
+    let first: &[u8] = {
+        let parts = [&data[..5], &data[5..]];
+        Segments::new(&parts).get_ref()[0]
+    };
+    assert_eq!(first, b"Hello");
+
+    let mut reader = SegmentIter::new(parts.iter().copied());
+    let mut buf = [0u8; 16];
+    assert!(matches!(reader.read(&mut buf), Ok(13)));
+    assert_eq!(&buf[..13], b"Hello, world!");
+    assert!(matches!(reader.fill_buf(), Ok(b"")));
+}
+
+#[test]
+fn ring_buffer() {
+    use not_io::{ErrorKind, RingBuffer};
+    let mut ring = RingBuffer::new([0u8; 8]);
+
+    assert_eq!(
+        ring.read(&mut [0; 4]).unwrap_err().kind(),
+        ErrorKind::WouldBlock
+    );
+    assert!(matches!(ring.write(b"Hello, "), Ok(7)));
+
+    let mut buf = [0u8; 5];
+    ring.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Hello");
+
+    // Wraps around the end of the storage.
+    assert!(matches!(ring.write(b"world!!"), Ok(6)));
+    assert!(ring.is_full());
+    assert_eq!(ring.write(b"!").unwrap_err().kind(), ErrorKind::WouldBlock);
+    assert_eq!(ring.as_slices(), (&b", w"[..], &b"orld!"[..]));
+    assert!(matches!(ring.fill_buf(), Ok(b", w")));
+
+    let mut out = [0u8; 8];
+    assert!(matches!(ring.read(&mut out), Ok(8)));
+    assert_eq!(&out, b", world!");
+    assert!(ring.is_empty());
+
+    ring.write_all(b"tail").unwrap();
+    ring.close();
+    assert_eq!(ring.write(b"x").unwrap_err().kind(), ErrorKind::BrokenPipe);
+
+    let mut reader = (&mut ring).take(2);
+    assert!(matches!(reader.read(&mut out), Ok(2)));
+    let mut target = [0u8; 4];
+    let mut writer = Cursor::new(&mut target[..]);
+    assert!(matches!(not_io::copy(&mut ring, &mut writer), Ok(2)));
+    assert!(matches!(ring.read(&mut out), Ok(0)));
+    assert_eq!(&target[..2], b"il");
+}
@@ -1,4 +1,4 @@
-use not_io::{AllowStd, BufRead, Cursor, Empty, Read, Repeat, Seek, Sink, Take, Write};
+use not_io::{AllowStd, BufRead, BufReader, Cursor, Empty, Read, Repeat, Seek, Sink, Take, Write};
 
 extern crate alloc;
 use alloc::{string::String, vec::Vec};
@@ -33,6 +33,12 @@ const XXX: () = {
     let _ = is_write::<&'static Sink>;
     let _ = is_read::<Take<Empty>>;
     let _ = is_buf_read::<Take<Empty>>;
+    let _ = is_buf_read::<BufReader<Empty, Vec<u8>>>;
+    let _ = is_seek::<BufReader<Cursor<Vec<u8>>, Vec<u8>>>;
+    let _ = is_write::<AllowStd<Cursor<Vec<u8>>>>;
+    let _ = is_write::<AllowStd<Cursor<&'static mut Vec<u8>>>>;
+    let _ = is_buf_read::<alloc::collections::VecDeque<u8>>;
+    let _ = is_write::<alloc::collections::VecDeque<u8>>;
 };
 
 #[test]
@@ -43,7 +49,7 @@ fn evaluate_consts() {
 #[test]
 fn read_to_buffer() {
     const SOURCE: &str = "Hello, world";
-    let ref mut source = SOURCE.as_bytes();
+    let source = &mut SOURCE.as_bytes();
     let elen = source.len();
     let mut buffer = Vec::new();
 
@@ -54,7 +60,7 @@ fn read_to_buffer() {
 #[test]
 fn read_to_string() {
     const SOURCE: &str = "Hello, world";
-    let ref mut source = SOURCE.as_bytes();
+    let source = &mut SOURCE.as_bytes();
     let elen = source.len();
     let mut buffer = String::new();
 
@@ -65,16 +71,16 @@ fn read_to_string() {
 #[test]
 fn read_to_fail() {
     const SOURCE: &[u8] = b"Hello, \xfeworld";
-    let ref mut source = &SOURCE[..];
+    let mut source = SOURCE;
     let mut buffer = String::new();
 
-    assert!(matches!(Read::read_to_string(source, &mut buffer), Err(_)));
+    assert!(Read::read_to_string(&mut source, &mut buffer).is_err());
 }
 
 #[test]
 fn read_buf() {
     const SOURCE: &str = "Hello, world";
-    let ref mut source = SOURCE.as_bytes();
+    let source = &mut SOURCE.as_bytes();
     assert!(matches!(source.fill_buf(), Ok(src) if src == SOURCE.as_bytes()));
 
     let mut buffer = Vec::new();
@@ -85,13 +91,27 @@ fn read_buf() {
 #[test]
 fn read_buf_to_string() {
     const SOURCE: &[u8] = b"Hello,\n\xfeworld";
-    let ref mut source = &SOURCE[..];
+    let mut source = SOURCE;
     let mut buffer = String::new();
 
     assert!(matches!(source.read_line(&mut buffer), Ok(7)));
     assert_eq!(buffer, "Hello,\n");
 
-    assert!(matches!(source.read_line(&mut buffer), Err(_)));
+    assert!(source.read_line(&mut buffer).is_err());
+}
+
+#[test]
+fn read_to_string_invalid_rollback() {
+    const SOURCE: &[u8] = b"world\xfe!";
+    let mut buffer = String::from("Hello, ");
+
+    let mut source = SOURCE;
+    assert!(Read::read_to_string(&mut source, &mut buffer).is_err());
+    assert_eq!(buffer, "Hello, ");
+
+    let mut source = SOURCE;
+    assert!(source.read_line(&mut buffer).is_err());
+    assert_eq!(buffer, "Hello, ");
 }
 
 #[test]
@@ -123,7 +143,7 @@ fn buf_writer_cursor_mid() {
 #[test]
 fn take_short() {
     const SOURCE: &[u8] = b"Hello, world";
-    let mut reader = Read::take(&SOURCE[..], 5);
+    let mut reader = Read::take(SOURCE, 5);
     let mut buffer = vec![0u8; 0];
 
     assert!(matches!(reader.fill_buf(), Ok(b"Hello")));
@@ -135,7 +155,7 @@ fn take_short() {
 #[test]
 fn take_long() {
     const SOURCE: &[u8] = b"Hello, world";
-    let mut reader = Read::take(&SOURCE[..], 0x42);
+    let mut reader = Read::take(SOURCE, 0x42);
     let mut buffer = vec![0u8; 0];
 
     assert!(matches!(reader.fill_buf(), Ok(SOURCE)));
@@ -143,3 +163,261 @@ fn take_long() {
     assert!(matches!(reader.fill_buf(), Ok(&[])));
     assert_eq!(buffer, SOURCE);
 }
+
+#[test]
+fn buf_reader_lines() {
+    const SOURCE: &[u8] = b"Hello,\nworld";
+    let mut reader = BufReader::new(SOURCE, vec![0u8; 4]);
+    let mut buffer = String::new();
+
+    assert!(matches!(reader.read_line(&mut buffer), Ok(7)));
+    assert_eq!(buffer, "Hello,\n");
+    assert!(matches!(reader.read_line(&mut buffer), Ok(5)));
+    assert_eq!(buffer, "Hello,\nworld");
+
+    let (_, storage) = reader.into_parts();
+    assert_eq!(storage.len(), 4);
+}
+
+#[test]
+fn write_vectored_vec() {
+    use not_io::IoSlice;
+    let bufs = [IoSlice::new(b"Hello"), IoSlice::new(b", world!")];
+
+    let mut buffer = Vec::new();
+    assert!(matches!(buffer.write_vectored(&bufs), Ok(13)));
+    assert_eq!(buffer, b"Hello, world!");
+
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.set_position(2);
+    assert!(matches!(cursor.write_vectored(&bufs), Ok(13)));
+    assert_eq!(cursor.position(), 15);
+    assert_eq!(&cursor.get_ref()[2..], b"Hello, world!");
+}
+
+#[test]
+fn split_and_lines() {
+    const SOURCE: &[u8] = b"GET / HTTP/1.1\r\nHost: example\n\nbody";
+    let reader = BufReader::new(SOURCE, vec![0u8; 4]);
+    let lines: Vec<String> = reader.lines().collect::<Result<_, _>>().unwrap();
+    assert_eq!(lines, ["GET / HTTP/1.1", "Host: example", "", "body"]);
+
+    let fields: Vec<Vec<u8>> = BufRead::split(&b"a,,b,"[..], b',')
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(fields, [&b"a"[..], b"", b"b"]);
+
+    let mut invalid = (&b"ok\n\xff\n"[..]).lines();
+    assert_eq!(invalid.next().unwrap().unwrap(), "ok");
+    assert!(invalid.next().unwrap().is_err());
+}
+
+#[test]
+fn read_to_end_uninit() {
+    let mut source = not_io::repeat(7).take(100_000);
+    let mut buffer = vec![1, 2, 3];
+    assert!(matches!(source.read_to_end(&mut buffer), Ok(100_000)));
+    assert_eq!(buffer.len(), 100_003);
+    assert_eq!(&buffer[..4], [1, 2, 3, 7]);
+    assert!(buffer[3..].iter().all(|&b| b == 7));
+
+    let mut buffer = Vec::with_capacity(4);
+    let mut source = Cursor::new("Hello, world!").chain(&b"!!"[..]);
+    assert!(matches!(source.read_to_end(&mut buffer), Ok(15)));
+    assert_eq!(buffer, b"Hello, world!!!");
+}
+
+#[test]
+fn read_to_end_bounded() {
+    use not_io::ErrorKind;
+
+    let mut buffer = vec![b'>'];
+    let mut source = &b"Hello"[..];
+    assert!(matches!(source.read_to_end_bounded(&mut buffer, 5), Ok(5)));
+    assert_eq!(buffer, b">Hello");
+
+    let mut buffer = Vec::new();
+    let mut source = not_io::repeat(0);
+    let err = source
+        .read_to_end_bounded(&mut buffer, 1 << 20)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert_eq!(buffer.len(), 1 << 20);
+
+    struct Boastful(&'static [u8]);
+
+    impl Read for Boastful {
+        fn read(&mut self, buf: &mut [u8]) -> not_io::Result<usize> {
+            self.0.read(buf)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, None)
+        }
+    }
+
+    let mut buffer = Vec::new();
+    assert!(matches!(Boastful(b"Hello").read_to_end(&mut buffer), Ok(5)));
+    assert_eq!(buffer, b"Hello");
+
+    // Without a buffer, the byte beyond the limit is consumed to find it.
+    let mut buffer = Vec::new();
+    let mut source = Boastful(b"Hello, world");
+    let err = source.read_to_end_bounded(&mut buffer, 5).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert_eq!(source.0, b" world");
+
+    // Buffered readers keep it.
+    let mut buffer = Vec::new();
+    let mut source = BufReader::new(Boastful(b"Hello, world"), vec![0u8; 4]);
+    let err = source.read_to_end_bounded(&mut buffer, 5).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert!(matches!(source.read_to_end(&mut buffer), Ok(7)));
+    assert_eq!(buffer, b"Hello, world");
+
+    let mut buffer = Vec::new();
+    let mut source = &b"Hello, world"[..];
+    let err = source.read_to_end_bounded(&mut buffer, 5).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert_eq!(source, b", world");
+
+    // Other errors keep the data read so far.
+    struct FailingRead;
+
+    impl Read for FailingRead {
+        fn read(&mut self, _: &mut [u8]) -> not_io::Result<usize> {
+            Err(ErrorKind::Other.into())
+        }
+    }
+
+    let mut buffer = vec![b'>'];
+    let mut source = Read::chain(&b"Hello"[..], FailingRead);
+    let err = source.read_to_end_bounded(&mut buffer, 100).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Other);
+    assert_eq!(buffer, b">Hello");
+
+    let mut buffer = Vec::new();
+    let mut source = not_io::repeat(0).take(10_000);
+    let err = source.read_to_end_bounded(&mut buffer, 1000).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert_eq!(buffer.len(), 1000);
+    assert!(buffer.capacity() <= 1000);
+
+    let mut text = String::from("> ");
+    let mut source = Cursor::new("Hello, world!");
+    let err = source.read_to_string_bounded(&mut text, 5).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert_eq!(text, "> Hello");
+
+    let mut source = Cursor::new("Hello");
+    assert!(matches!(
+        source.read_to_string_bounded(&mut text, 100),
+        Ok(5)
+    ));
+    assert_eq!(text, "> HelloHello");
+}
+
+#[test]
+fn forward_box() {
+    let mut reader: alloc::boxed::Box<dyn BufRead> =
+        alloc::boxed::Box::new(Cursor::new("Hello,\nworld"));
+    let mut line = String::new();
+    assert!(matches!(reader.read_line(&mut line), Ok(7)));
+    assert_eq!(reader.size_hint(), (5, Some(5)));
+    assert!(matches!(reader.read_to_string(&mut line), Ok(5)));
+    assert_eq!(line, "Hello,\nworld");
+
+    let mut seeker = alloc::boxed::Box::new(Cursor::new("Hello"));
+    assert!(matches!(Seek::stream_len(&mut seeker), Ok(5)));
+
+    let mut writer: alloc::boxed::Box<dyn Write> = alloc::boxed::Box::new(Vec::new());
+    write!(writer, "{}", 42).unwrap();
+}
+
+#[test]
+fn vec_deque_fifo() {
+    let mut fifo = alloc::collections::VecDeque::with_capacity(8);
+    fifo.write_all(b"abcdef").unwrap();
+
+    let mut buf = [0u8; 4];
+    assert!(matches!(fifo.read(&mut buf), Ok(4)));
+    assert_eq!(&buf, b"abcd");
+
+    // Wrap around the end of the ring buffer.
+    fifo.write_all(b"ghijk").unwrap();
+    let mut all = [0u8; 16];
+    assert!(matches!(fifo.read(&mut all), Ok(7)));
+    assert_eq!(&all[..7], b"efghijk");
+    assert!(matches!(fifo.read(&mut all), Ok(0)));
+
+    fifo.write_all(b"line\nrest").unwrap();
+    let mut line = String::new();
+    assert!(matches!(fifo.read_line(&mut line), Ok(5)));
+    assert_eq!(line, "line\n");
+    assert!(matches!(fifo.fill_buf(), Ok(b"rest")));
+    fifo.consume(10);
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn cursor_boxed_slice() {
+    let storage: alloc::boxed::Box<[u8]> = vec![0u8; 4].into_boxed_slice();
+    let mut cursor = Cursor::new(storage);
+    assert!(matches!(cursor.write(b"Hello"), Ok(4)));
+    assert_eq!(&cursor.get_ref()[..], b"Hell");
+    cursor.set_position(1);
+    assert_eq!(cursor.remaining_slice(), b"ell");
+
+    let mut allowed = AllowStd(Cursor::new([0u8; 2]));
+    assert!(matches!(allowed.write(b"Hi!"), Ok(2)));
+}
+
+#[test]
+fn pipe_and_duplex() {
+    use not_io::ErrorKind;
+
+    let (mut reader, mut writer) = not_io::pipe(4);
+    let mut buf = [0u8; 8];
+    assert_eq!(
+        reader.read(&mut buf).unwrap_err().kind(),
+        ErrorKind::WouldBlock
+    );
+    assert!(matches!(writer.write(b"Hello"), Ok(4)));
+    assert_eq!(
+        writer.write(b"o").unwrap_err().kind(),
+        ErrorKind::WouldBlock
+    );
+    assert!(matches!(reader.read(&mut buf), Ok(4)));
+    assert_eq!(&buf[..4], b"Hell");
+
+    writer.write_all(b"o!").unwrap();
+    drop(writer);
+    assert!(matches!(reader.read(&mut buf), Ok(2)));
+    assert!(matches!(reader.read(&mut buf), Ok(0)));
+
+    let (reader, mut writer) = not_io::pipe(4);
+    drop(reader);
+    assert_eq!(
+        writer.write(b"x").unwrap_err().kind(),
+        ErrorKind::BrokenPipe
+    );
+
+    let (mut client, mut server) = not_io::duplex(16);
+    client.write_all(b"PING").unwrap();
+    assert!(matches!(server.read(&mut buf), Ok(4)));
+    assert_eq!(&buf[..4], b"PING");
+    server.write_all(b"PONG").unwrap();
+    assert_eq!(
+        server.read(&mut buf).unwrap_err().kind(),
+        ErrorKind::WouldBlock
+    );
+
+    drop(server);
+    let mut reply = String::new();
+    assert!(matches!(client.read_to_string(&mut reply), Ok(4)));
+    assert_eq!(reply, "PONG");
+    assert_eq!(
+        client.write(b"x").unwrap_err().kind(),
+        ErrorKind::BrokenPipe
+    );
+}
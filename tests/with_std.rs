@@ -1,4 +1,4 @@
-use not_io::{AllowStd, Read, Write};
+use not_io::{AllowStd, NotIo, Read, Write};
 
 // Make sure that this includes the no-`alloc` subset of tests.
 #[path = "no_std.rs"]
@@ -6,6 +6,8 @@ mod _std;
 
 fn is_read<R: Read>() {}
 fn is_write<W: Write>() {}
+fn is_buf_read<R: not_io::BufRead>() {}
+fn is_seek<R: not_io::Seek>() {}
 
 const XXX: () = {
     #[allow(dead_code)]
@@ -16,9 +18,370 @@ const XXX: () = {
     fn generic_write<W: std::io::Write>() {
         let _ = is_write::<AllowStd<W>>;
     }
+    #[allow(dead_code)]
+    fn generic_buf_read<R: std::io::BufRead>() {
+        let _ = is_buf_read::<AllowStd<R>>;
+    }
+    #[allow(dead_code)]
+    fn generic_seek<S: std::io::Seek>() {
+        let _ = is_seek::<AllowStd<S>>;
+    }
 };
 
 #[test]
 fn evaluate_consts() {
     let _: () = XXX;
 }
+
+#[test]
+fn io_slice_layout() {
+    use core::mem::{align_of, size_of};
+    assert_eq!(size_of::<not_io::IoSlice>(), size_of::<std::io::IoSlice>());
+    assert_eq!(
+        align_of::<not_io::IoSlice>(),
+        align_of::<std::io::IoSlice>()
+    );
+    assert_eq!(
+        size_of::<not_io::IoSliceMut>(),
+        size_of::<std::io::IoSliceMut>()
+    );
+    assert_eq!(
+        align_of::<not_io::IoSliceMut>(),
+        align_of::<std::io::IoSliceMut>()
+    );
+}
+
+#[test]
+fn vectored_allow_std() {
+    use not_io::{IoSlice, IoSliceMut};
+    let mut writer = AllowStd(std::io::Cursor::new(Vec::new()));
+    let mut bufs = [IoSlice::new(b"Hello"), IoSlice::new(b", world!")];
+    writer.write_all_vectored(&mut bufs).unwrap();
+    assert_eq!(writer.0.get_ref(), b"Hello, world!");
+
+    let mut reader = AllowStd(std::io::Cursor::new(writer.0.into_inner()));
+    let (mut first, mut second) = ([0u8; 5], [0u8; 8]);
+    let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+    assert!(matches!(reader.read_vectored(&mut bufs), Ok(13)));
+    assert_eq!(&second, b", world!");
+}
+
+#[test]
+fn error_kind_from_std() {
+    use not_io::{Error, ErrorKind};
+    let err = Error::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    let err = Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+    assert_eq!(err.kind(), ErrorKind::Other);
+
+    let err = std::io::Error::from(Error::from(ErrorKind::UnexpectedEof));
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn error_kind_without_std() {
+    use not_io::{Error, ErrorKind};
+    let err = Error::from(ErrorKind::FileTooLarge);
+    assert_eq!(err.kind(), ErrorKind::FileTooLarge);
+    assert_eq!(err.static_message(), None);
+    assert_eq!(err.to_string(), "file too large");
+
+    let io = std::io::Error::from(err);
+    assert_eq!(io.kind(), std::io::ErrorKind::Other);
+    assert_eq!(io.to_string(), "file too large");
+    assert_eq!(Error::from(io).kind(), ErrorKind::FileTooLarge);
+
+    let io = std::io::Error::from(Error::from(ErrorKind::BufferFull));
+    assert_eq!(Error::from(io).kind(), ErrorKind::BufferFull);
+
+    let err = Error::from(ErrorKind::Other);
+    assert!(std::io::Error::from(err).get_ref().is_none());
+}
+
+#[test]
+fn error_static_round_trip() {
+    use not_io::{Error, ErrorKind};
+    let err = Error::new_static_code(ErrorKind::InvalidData, "bad magic number in header", 7);
+
+    let io = std::io::Error::from(err);
+    assert_eq!(io.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(io.to_string(), "bad magic number in header (code 7)");
+
+    let err = Error::from(io);
+    assert_eq!(err.static_message(), Some("bad magic number in header"));
+    assert_eq!(err.code(), Some(7));
+}
+
+#[test]
+fn error_trait() {
+    use not_io::{Error, ErrorKind};
+    let err: Box<dyn std::error::Error> = Box::new(Error::from(ErrorKind::InvalidData));
+    assert_eq!(err.to_string(), "invalid data");
+
+    #[derive(Debug)]
+    struct Context(Error);
+
+    impl std::fmt::Display for Context {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("bad header")
+        }
+    }
+
+    impl std::error::Error for Context {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let inner = Error::new_static(ErrorKind::InvalidData, "bad magic");
+    assert!(std::error::Error::source(&inner).is_none());
+
+    let io = std::io::Error::new(std::io::ErrorKind::InvalidData, Context(inner));
+    let outer = Error::from(io);
+    assert_eq!(outer.to_string(), "bad header");
+    let source = std::error::Error::source(&outer).unwrap();
+    assert_eq!(
+        source.downcast_ref::<Error>().unwrap().static_message(),
+        Some("bad magic")
+    );
+}
+
+#[test]
+fn allow_std_buf_read_seek() {
+    use not_io::{BufRead, Seek, SeekFrom};
+    let inner = std::io::Cursor::new(b"Hello,\nworld!".to_vec());
+    let mut stream = AllowStd(std::io::BufReader::new(inner));
+
+    let mut line = String::new();
+    assert!(matches!(stream.read_line(&mut line), Ok(7)));
+    assert_eq!(line, "Hello,\n");
+
+    assert!(matches!(stream.seek(SeekFrom::Current(-2)), Ok(5)));
+    assert!(matches!(stream.fill_buf(), Ok(b",\nworld!")));
+    assert!(matches!(stream.stream_position(), Ok(5)));
+}
+
+#[test]
+#[cfg(not_io_seek_relative)]
+fn seek_relative_forwarding() {
+    use not_io::{BufRead, BufReader, Seek, SeekFrom};
+
+    /// Counts the seeks that reach the underlying stream.
+    struct Counted<S> {
+        inner: S,
+        seeks: usize,
+    }
+
+    impl<S: std::io::Read> std::io::Read for Counted<S> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl<S: std::io::Seek> std::io::Seek for Counted<S> {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.seeks += 1;
+            self.inner.seek(pos)
+        }
+    }
+
+    let inner = Counted {
+        inner: std::io::Cursor::new(b"Hello, world!"),
+        seeks: 0,
+    };
+    let mut stream = AllowStd(std::io::BufReader::new(inner));
+    assert!(matches!(stream.fill_buf(), Ok(b"Hello, world!")));
+    stream.seek_relative(7).unwrap();
+    assert!(matches!(stream.fill_buf(), Ok(b"world!")));
+    assert_eq!(stream.0.get_ref().seeks, 0);
+
+    let inner = AllowStd(Counted {
+        inner: std::io::Cursor::new(b"Hello, world!"),
+        seeks: 0,
+    });
+    let mut stream = NotIo(BufReader::new(inner, [0u8; 16]));
+    assert!(matches!(stream.0.fill_buf(), Ok(b"Hello, world!")));
+    std::io::Seek::seek_relative(&mut stream, 7).unwrap();
+    assert!(matches!(stream.0.fill_buf(), Ok(b"world!")));
+    assert_eq!(stream.0.get_ref().0.seeks, 0);
+    assert!(matches!(stream.0.seek(SeekFrom::Start(0)), Ok(0)));
+}
+
+#[test]
+fn seek_from_conversion() {
+    use not_io::SeekFrom;
+    let pos = std::io::SeekFrom::from(SeekFrom::End(-1));
+    assert_eq!(pos, std::io::SeekFrom::End(-1));
+    assert_eq!(SeekFrom::from(pos), SeekFrom::End(-1));
+}
+
+#[test]
+fn cursor_std_traits() {
+    use std::io::{BufRead as _, Seek as _};
+    let mut cursor = not_io::Cursor::new("Hello, world!");
+
+    assert_eq!(cursor.seek(std::io::SeekFrom::End(-6)).unwrap(), 7);
+    assert_eq!(cursor.fill_buf().unwrap(), b"world!");
+    let mut rest = String::new();
+    assert_eq!(
+        std::io::Read::read_to_string(&mut cursor, &mut rest).unwrap(),
+        6
+    );
+    assert_eq!(rest, "world!");
+
+    let mut cursor = not_io::Cursor::new("Hello, world!");
+    let (mut first, mut second) = ([0u8; 5], [0u8; 16]);
+    let mut bufs = [
+        std::io::IoSliceMut::new(&mut first),
+        std::io::IoSliceMut::new(&mut second),
+    ];
+    assert_eq!(
+        std::io::Read::read_vectored(&mut cursor, &mut bufs).unwrap(),
+        13
+    );
+    assert_eq!(&first, b"Hello");
+    assert_eq!(&second[..8], b", world!");
+
+    let mut target = Vec::new();
+    let mut cursor = not_io::Cursor::new(&mut target);
+    std::io::copy(&mut &b"Hello"[..], &mut cursor).unwrap();
+    std::io::Write::flush(&mut cursor).unwrap();
+    assert_eq!(target, b"Hello");
+}
+
+#[test]
+fn not_io_into_std() {
+    use not_io::{Cursor, NotIo};
+    use std::io::{BufRead as _, Seek as _};
+
+    let mut source = NotIo(&b"Hello, world!"[..]);
+    let mut target = NotIo(Vec::new());
+    assert!(matches!(std::io::copy(&mut source, &mut target), Ok(13)));
+    assert_eq!(target.0, b"Hello, world!");
+
+    let mut reader = std::io::BufReader::new(NotIo(Cursor::new("Hello,\nworld!")));
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "Hello,\n");
+
+    assert!(matches!(reader.seek(std::io::SeekFrom::End(-1)), Ok(12)));
+    assert!(matches!(reader.fill_buf(), Ok(b"!")));
+}
+
+#[test]
+fn copy_dispatches_to_std() {
+    /// Records the largest buffer it was asked to fill.
+    struct Probe {
+        left: usize,
+        largest: usize,
+    }
+
+    impl std::io::Read for Probe {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.largest = self.largest.max(buf.len());
+            let len = buf.len().min(self.left);
+            buf[..len].fill(b'x');
+            self.left -= len;
+            Ok(len)
+        }
+    }
+
+    let mut source = AllowStd(Probe {
+        left: 10_000,
+        largest: 0,
+    });
+    let mut target = AllowStd(Vec::new());
+    assert!(matches!(not_io::copy(&mut source, &mut target), Ok(10_000)));
+    assert_eq!(target.0.len(), 10_000);
+    // The standard copy uses a larger buffer than our stack copy.
+    assert!(source.0.largest > 512);
+
+    let mut source = AllowStd(Probe {
+        left: 10_000,
+        largest: 0,
+    });
+    let mut target = not_io::sink();
+    assert!(matches!(not_io::copy(&mut source, &mut target), Ok(10_000)));
+    assert_eq!(source.0.largest, 512);
+
+    // Forwarding wrappers keep the dispatch.
+    let mut source = NotIo(AllowStd(Probe {
+        left: 10_000,
+        largest: 0,
+    }));
+    let mut target: Box<dyn not_io::Write> = Box::new(NotIo(AllowStd(Vec::new())));
+    assert!(matches!(
+        not_io::copy(&mut &mut source, &mut target),
+        Ok(10_000)
+    ));
+    assert!(source.0 .0.largest > 512);
+}
+
+#[test]
+fn blocking_pipe_threads() {
+    let (mut reader, mut writer) = not_io::blocking_pipe(3);
+
+    let producer = std::thread::spawn(move || {
+        for chunk in [&b"Hello, "[..], b"world", b"!"] {
+            writer.write_all(chunk).unwrap();
+        }
+    });
+
+    let mut received = Vec::new();
+    assert!(matches!(reader.read_to_end(&mut received), Ok(13)));
+    assert_eq!(received, b"Hello, world!");
+    producer.join().unwrap();
+
+    let (reader, mut writer) = not_io::blocking_pipe(4);
+    writer.write_all(b"ab").unwrap();
+    assert_eq!(reader.size_hint(), (2, None));
+    drop(writer);
+    assert_eq!(reader.size_hint(), (2, Some(2)));
+
+    let (reader, mut writer) = not_io::blocking_pipe(1);
+    let consumer = std::thread::spawn(move || drop(reader));
+    consumer.join().unwrap();
+    let err = writer.write_all(b"lost").unwrap_err();
+    assert_eq!(err.kind(), not_io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn line_writer_panic() {
+    struct PanicOnce<'a> {
+        panicked: bool,
+        written: &'a mut Vec<u8>,
+    }
+
+    impl Write for PanicOnce<'_> {
+        fn write(&mut self, buf: &[u8]) -> not_io::Result<usize> {
+            if !self.panicked {
+                self.panicked = true;
+                panic!("inner writer failed");
+            }
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> not_io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut written = Vec::new();
+    let inner = PanicOnce {
+        panicked: false,
+        written: &mut written,
+    };
+    let mut writer = not_io::LineWriter::new(inner, [0u8; 16]);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        writer.write(b"Hello,\n").map(drop)
+    }));
+    assert!(result.is_err());
+
+    // The writer is not retried on drop after it panicked.
+    writer.write_all(b"world").unwrap();
+    drop(writer);
+    assert!(written.is_empty());
+}